@@ -0,0 +1,52 @@
+use rs_openai::{
+    interfaces::assistants::{CreateAssistantRequestBuilder, ListAssistantRequestBuilder},
+    OpenAI,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = OpenAI::from_dotenv()?;
+
+    // create
+    let req = CreateAssistantRequestBuilder::default()
+        .model("gpt-4o")
+        .name("Math Tutor")
+        .instructions("You are a personal math tutor. Write and run code to answer math questions.")
+        .build()?;
+
+    let res = client.assistants().create_assistant(&req).await?;
+    println!("{:?}", res);
+
+    // list
+    let req = ListAssistantRequestBuilder::default().build()?;
+    let res = client.assistants().list_assistants(&req).await?;
+    println!("{:?}", res);
+
+    // retrieve
+    let res = client
+        .assistants()
+        .retrieve_assistant("YOUR_ASSISTANT_ID")
+        .await?;
+    println!("{:?}", res);
+
+    // modify
+    let req = CreateAssistantRequestBuilder::default()
+        .model("gpt-4o")
+        .name("Algebra Tutor")
+        .build()?;
+
+    let res = client
+        .assistants()
+        .modify_assistant("YOUR_ASSISTANT_ID", &req)
+        .await?;
+    println!("{:?}", res);
+
+    // delete
+    let res = client
+        .assistants()
+        .delete_assistant("YOUR_ASSISTANT_ID")
+        .await?;
+    println!("{:?}", res);
+
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use dotenvy::dotenv;
 use rs_openai::{
     audio::{
         AudioModel, CreateTranscriptionRequestBuilder, CreateTranslationRequestBuilder, Language,
@@ -7,17 +6,11 @@ use rs_openai::{
     shared::types::FileMeta,
     OpenAI,
 };
+use std::fs::File;
 use std::io::prelude::*;
-use std::{env::var, fs::File};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
+    let client = OpenAI::from_dotenv()?;
 
     let mut file = File::open("./assets/dear_abe_san.mp4").unwrap();
     let mut buffer = Vec::new();
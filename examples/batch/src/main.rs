@@ -0,0 +1,34 @@
+use rs_openai::{
+    interfaces::batch::{CreateBatchRequestBuilder, ListBatchRequestBuilder},
+    OpenAI,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = OpenAI::from_dotenv()?;
+
+    // create, the input file has to be uploaded with the purpose `batch` first
+    let req = CreateBatchRequestBuilder::default()
+        .input_file_id("YOUR_FILE_ID")
+        .endpoint("/v1/chat/completions")
+        .completion_window("24h")
+        .build()?;
+
+    let res = client.batches().create(&req).await?;
+    println!("{:?}", res);
+
+    // list
+    let req = ListBatchRequestBuilder::default().build()?;
+    let res = client.batches().list(&req).await?;
+    println!("{:?}", res);
+
+    // retrieve
+    let res = client.batches().retrieve("YOUR_BATCH_ID".into()).await?;
+    println!("{:?}", res);
+
+    // cancel
+    let res = client.batches().cancel("YOUR_BATCH_ID".into()).await?;
+    println!("{:?}", res);
+
+    Ok(())
+}
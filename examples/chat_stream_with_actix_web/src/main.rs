@@ -1,15 +1,16 @@
 use actix_cors::Cors;
-use actix_web::{get, http::header, middleware, web, App, HttpRequest, HttpServer, Responder};
-use actix_web_lab::sse;
-use dotenvy::dotenv;
-use futures::StreamExt;
+use actix_web::{
+    error::{ErrorBadGateway, ErrorBadRequest},
+    get,
+    http::header,
+    middleware, web, App, HttpServer,
+};
 use rs_openai::{
-    chat::{ChatCompletionMessageRequestBuilder, CreateChatRequestBuilder, Role},
+    interfaces::chat::{ChatCompletionMessageRequestBuilder, CreateChatRequestBuilder, Role},
+    relay::SseRelay,
     OpenAI,
 };
 use serde::Deserialize;
-use serde_json::json;
-use std::env::var;
 use std::time::Duration;
 
 #[derive(Debug, Deserialize)]
@@ -19,73 +20,42 @@ struct Params {
 }
 
 #[get("/create_chat")]
-async fn create_chat(req: HttpRequest) -> impl Responder {
-    let params = web::Query::<Params>::from_query(req.query_string()).unwrap();
-
-    let (tx, rx) = sse::channel(1024);
-
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
-
+async fn create_chat(
+    client: web::Data<OpenAI>,
+    params: web::Query<Params>,
+) -> actix_web::Result<SseRelay> {
     let req = CreateChatRequestBuilder::default()
         .model("gpt-3.5-turbo")
         .messages(vec![ChatCompletionMessageRequestBuilder::default()
             .role(Role::User)
-            .content(&params.q)
+            .content(params.q.as_str())
             .build()
-            .unwrap()])
+            .map_err(ErrorBadRequest)?])
         .stream(true)
         .user(&params.user_id)
         .build()
-        .unwrap();
+        .map_err(ErrorBadRequest)?;
 
-    let mut count = 0;
-    let mut stream = client.chat().create_with_stream(&req).await.unwrap();
+    let stream = client
+        .chat()
+        .create_with_stream(&req)
+        .await
+        .map_err(ErrorBadGateway)?;
 
-    actix_web::rt::spawn(async move {
-        while let Some(response) = stream.next().await {
-            if let Ok(res) = response {
-                let id = res.id;
-
-                for choice in res.choices {
-                    if let Some(content) = choice.delta.content {
-                        count += 1;
-                        let _ = tx
-                            .send(
-                                sse::Data::new(
-                                    json! ({
-                                        "q": &params.q,
-                                        "a": content,
-                                        "id": id
-                                    })
-                                    .to_string(),
-                                )
-                                .event("chat/completions")
-                                .id(count.to_string()),
-                            )
-                            .await;
-                    }
-                }
-            }
-        }
-    });
-
-    rx.with_keep_alive(Duration::from_secs(5))
-        .with_retry_duration(Duration::from_secs(5))
+    Ok(SseRelay::new(stream).keep_alive(Duration::from_secs(5)))
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
+    let client = OpenAI::from_dotenv().unwrap();
+
     log::info!("starting HTTP server at http://localhost:12345");
 
-    HttpServer::new(|| {
+    HttpServer::new(move || {
         App::new()
+            .app_data(web::Data::new(client.clone()))
             .wrap(
                 Cors::default()
                     .allow_any_origin()
@@ -1,16 +1,8 @@
-use dotenvy::dotenv;
 use rs_openai::{embeddings::CreateEmbeddingRequestBuilder, OpenAI};
-use std::env::var;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
+    let client = OpenAI::from_dotenv()?;
 
     let req = CreateEmbeddingRequestBuilder::default()
         .model("text-embedding-ada-002")
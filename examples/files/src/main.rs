@@ -1,17 +1,10 @@
-use dotenvy::dotenv;
 use rs_openai::{files::UploadFileRequestBuilder, shared::types::FileMeta, OpenAI};
+use std::fs::File;
 use std::io::prelude::*;
-use std::{env::var, fs::File};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
+    let client = OpenAI::from_dotenv()?;
 
     // list
     let res = client.files().list().await?;
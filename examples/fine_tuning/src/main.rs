@@ -1,18 +1,10 @@
-use dotenvy::dotenv;
 use futures::StreamExt;
 use rs_openai::{fine_tuning::CreateFineTuningRequestBuilder, OpenAI};
-use std::env::var;
 use std::io::{stdout, Write};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
+    let client = OpenAI::from_dotenv()?;
 
     // create
     let req = CreateFineTuningRequestBuilder::default()
@@ -0,0 +1,56 @@
+use rs_openai::{
+    interfaces::messages::{
+        Content, CreateMessageRequestBuilder, ListMessageRequestBuilder,
+        ModifyMessageRequestBuilder,
+    },
+    OpenAI,
+};
+use std::collections::HashMap;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = OpenAI::from_dotenv()?;
+
+    // create
+    let req = CreateMessageRequestBuilder::default()
+        .role("user")
+        .content(Content::TextContent(
+            "How does AI work? Explain it in simple terms.".to_string(),
+        ))
+        .build()?;
+
+    let res = client.messages().create("YOUR_THREAD_ID", &req).await?;
+    println!("{:?}", res);
+
+    // list
+    let req = ListMessageRequestBuilder::default().build()?;
+    let res = client.messages().list("YOUR_THREAD_ID", &req).await?;
+    println!("{:?}", res);
+
+    // retrieve
+    let res = client
+        .messages()
+        .retrieve("YOUR_THREAD_ID", "YOUR_MESSAGE_ID")
+        .await?;
+    println!("{:?}", res);
+
+    // modify
+    let req = ModifyMessageRequestBuilder::default()
+        .metadata(HashMap::from([("modified".to_string(), "true".into())]))
+        .build()?;
+
+    let res = client
+        .messages()
+        .modify("YOUR_THREAD_ID", "YOUR_MESSAGE_ID", &req)
+        .await?;
+    println!("{:?}", res);
+
+    // delete
+    let res = client
+        .messages()
+        .delete("YOUR_THREAD_ID", "YOUR_MESSAGE_ID")
+        .await?;
+    println!("{:?}", res);
+
+    Ok(())
+}
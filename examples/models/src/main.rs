@@ -1,16 +1,8 @@
-use dotenvy::dotenv;
 use rs_openai::OpenAI;
-use std::env::var;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
+    let client = OpenAI::from_dotenv()?;
 
     let list = client.models().list().await?;
     println!("{:?}", list);
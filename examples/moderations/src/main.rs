@@ -1,19 +1,8 @@
-use dotenvy::dotenv;
-use rs_openai::{
-    moderations::CreateModerationRequestBuilder,
-    OpenAI,
-};
-use std::env::var;
+use rs_openai::{moderations::CreateModerationRequestBuilder, OpenAI};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
-    let api_key = var("OPENAI_API_KEY").unwrap();
-
-    let client = OpenAI::new(&OpenAI {
-        api_key,
-        org_id: None,
-    });
+    let client = OpenAI::from_dotenv()?;
 
     // create
     let req = CreateModerationRequestBuilder::default()
@@ -0,0 +1,48 @@
+use rs_openai::{
+    interfaces::runs::{CreateRunRequestBuilder, ModifyRunRequestBuilder},
+    OpenAI,
+};
+use std::collections::HashMap;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = OpenAI::from_dotenv()?;
+
+    // create
+    let req = CreateRunRequestBuilder::default()
+        .assistant_id("YOUR_ASSISTANT_ID")
+        .build()?;
+
+    let res = client.runs().create("YOUR_THREAD_ID", &req).await?;
+    println!("{:?}", res);
+
+    // retrieve
+    let res = client
+        .runs()
+        .retrieve("YOUR_THREAD_ID", "YOUR_RUN_ID")
+        .await?;
+    println!("{:?}", res);
+
+    // modify
+    let req = ModifyRunRequestBuilder::default()
+        .metadata(HashMap::from([(
+            "user_id".to_string(),
+            "user_abc123".into(),
+        )]))
+        .build()?;
+
+    let res = client
+        .runs()
+        .modify("YOUR_THREAD_ID", "YOUR_RUN_ID", &req)
+        .await?;
+    println!("{:?}", res);
+
+    // cancel
+    let res = client
+        .runs()
+        .cancel("YOUR_THREAD_ID", "YOUR_RUN_ID")
+        .await?;
+    println!("{:?}", res);
+
+    Ok(())
+}
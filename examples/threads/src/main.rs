@@ -0,0 +1,33 @@
+use rs_openai::{
+    interfaces::threads::{CreateThreadRequestBuilder, ModifyThreadRequestBuilder},
+    OpenAI,
+};
+use std::collections::HashMap;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = OpenAI::from_dotenv()?;
+
+    // create
+    let req = CreateThreadRequestBuilder::default().build()?;
+    let res = client.threads().create(&req).await?;
+    println!("{:?}", res);
+
+    // retrieve
+    let res = client.threads().retrieve("YOUR_THREAD_ID").await?;
+    println!("{:?}", res);
+
+    // modify
+    let req = ModifyThreadRequestBuilder::default()
+        .metadata(HashMap::from([("user".to_string(), "abc123".into())]))
+        .build()?;
+
+    let res = client.threads().modify("YOUR_THREAD_ID", &req).await?;
+    println!("{:?}", res);
+
+    // delete
+    let res = client.threads().delete("YOUR_THREAD_ID").await?;
+    println!("{:?}", res);
+
+    Ok(())
+}
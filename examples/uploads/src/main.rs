@@ -0,0 +1,61 @@
+use rs_openai::{
+    interfaces::uploads::{
+        AddUploadPartRequestBuilder, CompleteUploadBuilder, UploadFileRequestBuilder,
+    },
+    shared::types::File,
+    OpenAI,
+};
+use std::fs;
+use std::io::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = OpenAI::from_dotenv()?;
+
+    let mut file = fs::File::open("./assets/batch_requests.jsonl").unwrap();
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+
+    // upload
+    let req = UploadFileRequestBuilder::default()
+        .file(File {
+            filename: "batch_requests.jsonl".into(),
+            buffer: buffer.clone(),
+        })
+        .purpose("batch")
+        .build()?;
+
+    let res = client.uploads().upload(&req).await?;
+    println!("{:?}", res);
+
+    // add_upload_part
+    let req = AddUploadPartRequestBuilder::default()
+        .data(File {
+            filename: "batch_requests.jsonl".into(),
+            buffer,
+        })
+        .build()?;
+
+    let res = client
+        .uploads()
+        .add_upload_part("YOUR_UPLOAD_ID", &req)
+        .await?;
+    println!("{:?}", res);
+
+    // complete_upload
+    let req = CompleteUploadBuilder::default()
+        .part_ids(vec!["YOUR_PART_ID".to_string()])
+        .build()?;
+
+    let res = client
+        .uploads()
+        .complete_upload("YOUR_UPLOAD_ID", &req)
+        .await?;
+    println!("{:?}", res);
+
+    // cancel_upload
+    let res = client.uploads().cancel_upload("YOUR_UPLOAD_ID").await?;
+    println!("{:?}", res);
+
+    Ok(())
+}
@@ -6,13 +6,16 @@ use crate::client::OpenAI;
 use crate::interfaces::assistants;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Assistants<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Assistants {
+    openai: OpenAI,
 }
 
-impl<'a> Assistants<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Assistants {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Create an assistant with a model and instructions.
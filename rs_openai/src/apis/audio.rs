@@ -3,16 +3,19 @@
 use crate::client::OpenAI;
 use crate::interfaces::audio;
 use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use crate::shared::types::FormData;
 use crate::shared::utils::generate_random_string;
-use reqwest::multipart::Form;
 
-pub struct Audio<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Audio {
+    openai: OpenAI,
 }
 
-impl<'a> Audio<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Audio {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Generates audio from the input text.
@@ -88,14 +91,9 @@ impl<'a> Audio<'a> {
             .await
     }
 
-    fn create_transcription_form(&self, req: &audio::CreateTranscriptionRequest) -> Form {
-        let file_part = reqwest::multipart::Part::stream(req.file.buffer.clone())
-            .file_name(req.file.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-
-        let mut form = Form::new()
-            .part("file", file_part)
+    fn create_transcription_form(&self, req: &audio::CreateTranscriptionRequest) -> FormData {
+        let mut form = FormData::new()
+            .file("file", &req.file)
             .text("model", req.model.to_string());
 
         if let Some(prompt) = req.prompt.clone() {
@@ -123,17 +121,12 @@ impl<'a> Audio<'a> {
             }
         }
 
-        form
+        form.extra(&req.extra)
     }
 
-    fn create_translation_form(&self, req: &audio::CreateTranslationRequest) -> Form {
-        let file_part = reqwest::multipart::Part::stream(req.file.buffer.clone())
-            .file_name(req.file.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-
-        let mut form = Form::new()
-            .part("file", file_part)
+    fn create_translation_form(&self, req: &audio::CreateTranslationRequest) -> FormData {
+        let mut form = FormData::new()
+            .file("file", &req.file)
             .text("model", req.model.to_string());
 
         if let Some(prompt) = req.prompt.clone() {
@@ -148,7 +141,7 @@ impl<'a> Audio<'a> {
             form = form.text("temperature", temperature.to_string());
         }
 
-        form
+        form.extra(&req.extra)
     }
 
     fn is_json_type(&self, format_type: Option<audio::SttResponseFormat>) -> bool {
@@ -5,13 +5,16 @@ use crate::client::OpenAI;
 use crate::interfaces::batch;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Batch<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Batch {
+    openai: OpenAI,
 }
 
-impl<'a> Batch<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Batch {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates and executes a batch from an uploaded file of requests
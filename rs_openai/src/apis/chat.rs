@@ -1,19 +1,29 @@
 //! Given a chat conversation, the model will return a chat completion response.
 
 use crate::client::OpenAI;
+#[cfg(feature = "schemars")]
+use crate::interfaces::assistants::JsonSchema;
 use crate::interfaces::chat;
 use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
 use crate::shared::utils::is_stream;
+#[cfg(feature = "schemars")]
+use crate::shared::utils::{json_schema_for, strict_json_schema};
+use crate::tools::{ToolRegistry, ToolRun};
 use futures::Stream;
+#[cfg(feature = "schemars")]
+use serde::de::DeserializeOwned;
 use std::pin::Pin;
 
-pub struct Chat<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Chat {
+    openai: OpenAI,
 }
 
-impl<'a> Chat<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Chat {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates a completion for the chat message.
@@ -30,6 +40,48 @@ impl<'a> Chat<'a> {
         self.openai.post("/chat/completions", req).await
     }
 
+    /// Creates a completion whose content matches the JSON Schema of `T` and deserializes it.
+    ///
+    /// The `response_format` of `req` is replaced by the schema of `T` in strict mode, named after `T`.
+    #[cfg(feature = "schemars")]
+    pub async fn create_parsed<T>(
+        &self,
+        req: &chat::CreateChatRequest,
+    ) -> OpenAIResponse<chat::ParsedChatResponse<T>>
+    where
+        T: DeserializeOwned + schemars::JsonSchema,
+    {
+        let mut schema = json_schema_for::<T>();
+        strict_json_schema(&mut schema);
+
+        let mut req = req.clone();
+        req.response_format = Some(chat::ResponseFormat::JsonSchema {
+            json_schema: JsonSchema {
+                description: None,
+                name: schema_name::<T>(),
+                schema: Some(schema),
+                strict: Some(true),
+            },
+        });
+
+        let response = self.create(&req).await?;
+        let message = response.choices.first().map(|choice| &choice.message);
+
+        let refusal = message.and_then(|message| message.refusal.clone());
+        let parsed = match message.and_then(|message| message.content.as_ref()) {
+            Some(content) if refusal.is_none() => {
+                Some(serde_json::from_str(&content.text()).map_err(OpenAIError::JSONDeserialize)?)
+            }
+            _ => None,
+        };
+
+        Ok(chat::ParsedChatResponse {
+            parsed,
+            refusal,
+            response,
+        })
+    }
+
     /// Creates a completion for the chat message.
     pub async fn create_with_stream(
         &self,
@@ -44,6 +96,202 @@ impl<'a> Chat<'a> {
             ));
         }
 
-        Ok(self.openai.post_stream("/chat/completions", req).await)
+        self.openai.post_stream("/chat/completions", req).await
+    }
+
+    /// Creates completions and runs the tool calls of the model with `registry`, until the model answers without calling one.
+    ///
+    /// The functions of `registry` are sent as `tools` unless the request sets its own, only the first choice is followed.
+    /// Fails with [`OpenAIError::ToolIterationLimit`] if the model still calls tools after `max_iterations` completions.
+    pub async fn run_until_done(
+        &self,
+        req: &chat::CreateChatRequest,
+        registry: &ToolRegistry,
+        max_iterations: usize,
+    ) -> OpenAIResponse<ToolRun> {
+        let mut req = req.clone();
+        if req.tools.is_none() {
+            req.tools = Some(registry.tools());
+        }
+
+        for _ in 0..max_iterations {
+            let response = self.create(&req).await?;
+            let Some(message) = response
+                .choices
+                .first()
+                .map(|choice| choice.message.clone())
+            else {
+                return Ok(ToolRun {
+                    response,
+                    messages: req.messages,
+                });
+            };
+
+            let tool_calls = message.tool_calls.clone().unwrap_or_default();
+            req.messages.push(message);
+
+            if tool_calls.is_empty() {
+                return Ok(ToolRun {
+                    response,
+                    messages: req.messages,
+                });
+            }
+
+            req.messages.extend(registry.call_all(&tool_calls).await);
+        }
+
+        Err(OpenAIError::ToolIterationLimit(max_iterations))
+    }
+}
+
+/// The name of the schema of `T`, restricted to the characters allowed by the API.
+#[cfg(feature = "schemars")]
+fn schema_name<T>() -> String
+where
+    T: schemars::JsonSchema,
+{
+    T::schema_name()
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' => c,
+            _ => '_',
+        })
+        .take(64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::chat::{
+        ChatCompletionMessageRequestBuilder, CreateChatRequestBuilder, Role,
+    };
+    use crate::transport::fake::{client, Fake};
+    use serde::Deserialize;
+
+    const TOOL_CALLS: &str = r#"{"id":"chatcmpl-1","object":"chat.completion","created":0,"model":"gpt-4o","choices":[{"index":0,"finish_reason":"tool_calls","message":{"role":"assistant","content":null,"tool_calls":[{"id":"call_1","type":"function","function":{"name":"echo","arguments":"{\"text\":\"one\"}"}},{"id":"call_2","type":"function","function":{"name":"echo","arguments":"{\"text\":\"two\"}"}}]}}],"usage":{"prompt_tokens":10,"completion_tokens":5,"total_tokens":15}}"#;
+    const ANSWER: &str = r#"{"id":"chatcmpl-2","object":"chat.completion","created":0,"model":"gpt-4o","choices":[{"index":0,"finish_reason":"stop","message":{"role":"assistant","content":"one two"}}],"usage":{"prompt_tokens":20,"completion_tokens":2,"total_tokens":22}}"#;
+
+    #[derive(Deserialize)]
+    struct Echo {
+        text: String,
+    }
+
+    fn registry() -> ToolRegistry {
+        let mut registry = ToolRegistry::new();
+        registry.register_with_schema(
+            "echo",
+            "Echoes the text",
+            serde_json::json!({"type": "object"}),
+            |args: Echo| async move { Ok::<_, String>(args.text) },
+        );
+        registry
+    }
+
+    fn request() -> chat::CreateChatRequest {
+        CreateChatRequestBuilder::default()
+            .model("gpt-4o")
+            .messages(vec![ChatCompletionMessageRequestBuilder::default()
+                .role(Role::User)
+                .content("Say one two")
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn run_until_done_sends_the_results_back_until_the_model_answers() {
+        let fake = Fake::default()
+            .respond(200, TOOL_CALLS)
+            .respond(200, ANSWER);
+
+        let run = client(&fake)
+            .chat()
+            .run_until_done(&request(), &registry(), 5)
+            .await
+            .unwrap();
+
+        assert_eq!(run.response.id, "chatcmpl-2");
+        let roles = run
+            .messages
+            .iter()
+            .map(|message| format!("{:?}", message.role))
+            .collect::<Vec<_>>();
+        assert_eq!(roles, ["User", "Assistant", "Tool", "Tool", "Assistant"]);
+
+        let bodies = fake.json_bodies();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0]["tools"][0]["function"]["name"], "echo");
+        let messages = &bodies[1]["messages"];
+        assert_eq!(messages[1]["tool_calls"][1]["id"], "call_2");
+        assert_eq!(messages[2]["role"], "tool");
+        assert_eq!(messages[2]["tool_call_id"], "call_1");
+        assert_eq!(messages[2]["content"], "one");
+        assert_eq!(messages[3]["tool_call_id"], "call_2");
+        assert_eq!(messages[3]["content"], "two");
+    }
+
+    #[tokio::test]
+    async fn run_until_done_keeps_the_tools_of_the_request() {
+        let fake = Fake::default().respond(200, ANSWER);
+        let mut req = request();
+        req.tools = Some(vec![]);
+
+        client(&fake)
+            .chat()
+            .run_until_done(&req, &registry(), 5)
+            .await
+            .unwrap();
+
+        assert_eq!(fake.json_bodies()[0]["tools"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn run_until_done_gives_up_after_max_iterations() {
+        let fake = Fake::default()
+            .respond(200, TOOL_CALLS)
+            .respond(200, TOOL_CALLS)
+            .respond(200, ANSWER);
+
+        let error = client(&fake)
+            .chat()
+            .run_until_done(&request(), &registry(), 2)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, OpenAIError::ToolIterationLimit(2)));
+        assert_eq!(fake.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn create_with_stream_fails_on_an_error_status() {
+        let invalid_key = r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","code":"invalid_api_key"}}"#;
+        let no_quota = r#"{"error":{"message":"no quota","type":"insufficient_quota","code":"insufficient_quota"}}"#;
+        let mut req = request();
+        req.stream = Some(true);
+
+        for (status, body) in [(401, invalid_key), (429, no_quota)] {
+            let fake = Fake::default().respond(status, body);
+
+            let error = client(&fake)
+                .chat()
+                .create_with_stream(&req)
+                .await
+                .err()
+                .unwrap();
+
+            let error = error.api_error().unwrap();
+            assert_eq!(error.status.as_u16(), status);
+            assert_eq!(
+                error.message(),
+                if status == 401 {
+                    "Incorrect API key provided"
+                } else {
+                    "no quota"
+                }
+            );
+            assert_eq!(fake.requests()[0].headers["accept"], "text/event-stream");
+        }
     }
 }
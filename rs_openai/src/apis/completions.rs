@@ -7,13 +7,16 @@ use crate::shared::utils::is_stream;
 use futures::Stream;
 use std::pin::Pin;
 
-pub struct Completions<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Completions {
+    openai: OpenAI,
 }
 
-impl<'a> Completions<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Completions {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates a completion for the provided prompt and parameters.
@@ -44,6 +47,6 @@ impl<'a> Completions<'a> {
             ));
         }
 
-        Ok(self.openai.post_stream("/completions", req).await)
+        self.openai.post_stream("/completions", req).await
     }
 }
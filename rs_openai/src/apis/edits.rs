@@ -4,13 +4,16 @@ use crate::client::OpenAI;
 use crate::interfaces::edits;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Edits<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Edits {
+    openai: OpenAI,
 }
 
-impl<'a> Edits<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Edits {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates a new edit for the provided input, instruction, and parameters.
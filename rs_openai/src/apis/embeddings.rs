@@ -6,13 +6,16 @@ use crate::client::OpenAI;
 use crate::interfaces::embeddings;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Embeddings<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Embeddings {
+    openai: OpenAI,
 }
 
-impl<'a> Embeddings<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Embeddings {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates an embedding vector representing the input text.
@@ -10,13 +10,16 @@ use crate::client::OpenAI;
 use crate::interfaces::engines;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Engines<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Engines {
+    openai: OpenAI,
 }
 
-impl<'a> Engines<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Engines {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Lists the currently available (non-finetuned) models,
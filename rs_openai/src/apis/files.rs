@@ -3,15 +3,18 @@
 use crate::client::OpenAI;
 use crate::interfaces::files;
 use crate::shared::response_wrapper::OpenAIResponse;
-use reqwest::multipart::Form;
+use crate::shared::types::FormData;
 
-pub struct Files<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Files {
+    openai: OpenAI,
 }
 
-impl<'a> Files<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Files {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
     /// Returns a list of files that belong to the user's organization.
     pub async fn list(&self) -> OpenAIResponse<files::FileListResponse> {
@@ -25,14 +28,10 @@ impl<'a> Files<'a> {
         &self,
         req: &files::UploadFileRequest,
     ) -> OpenAIResponse<files::FileResponse> {
-        let file_part = reqwest::multipart::Part::stream(req.file.buffer.clone())
-            .file_name(req.file.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-
-        let form = Form::new()
-            .part("file", file_part)
-            .text("purpose", req.purpose.to_string());
+        let form = FormData::new()
+            .file("file", &req.file)
+            .text("purpose", req.purpose.to_string())
+            .extra(&req.extra);
 
         self.openai.post_form("/files", form).await
     }
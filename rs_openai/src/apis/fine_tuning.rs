@@ -4,13 +4,16 @@ use crate::client::OpenAI;
 use crate::interfaces::fine_tuning;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct FineTuning<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct FineTuning {
+    openai: OpenAI,
 }
 
-impl<'a> FineTuning<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl FineTuning {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates a job that fine-tunes a specified model from a given dataset.
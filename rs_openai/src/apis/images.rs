@@ -5,15 +5,18 @@
 use crate::client::OpenAI;
 use crate::interfaces::images;
 use crate::shared::response_wrapper::OpenAIResponse;
-use reqwest::multipart::Form;
+use crate::shared::types::FormData;
 
-pub struct Images<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Images {
+    openai: OpenAI,
 }
 
-impl<'a> Images<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Images {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates an image given a prompt.
@@ -29,22 +32,12 @@ impl<'a> Images<'a> {
         &self,
         req: &images::CreateImageEditRequest,
     ) -> OpenAIResponse<images::ImageResponse> {
-        let file_part = reqwest::multipart::Part::stream(req.image.buffer.clone())
-            .file_name(req.image.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-
-        let mut form = Form::new()
-            .part("image", file_part)
+        let mut form = FormData::new()
+            .file("image", &req.image)
             .text("prompt", req.prompt.to_string());
 
-        if let Some(mask) = req.mask.clone() {
-            let file_part = reqwest::multipart::Part::stream(req.image.buffer.clone())
-                .file_name(mask.filename)
-                .mime_str("application/octet-stream")
-                .unwrap();
-
-            form = form.part("mask", file_part);
+        if let Some(mask) = &req.mask {
+            form = form.file("mask", mask);
         }
 
         if let Some(n) = req.n {
@@ -63,6 +56,8 @@ impl<'a> Images<'a> {
             form = form.text("user", user);
         }
 
+        form = form.extra(&req.extra);
+
         self.openai.post_form("/images/edits", form).await
     }
 
@@ -71,12 +66,7 @@ impl<'a> Images<'a> {
         &self,
         req: &images::CreateImageVariationRequest,
     ) -> OpenAIResponse<images::ImageResponse> {
-        let file_part = reqwest::multipart::Part::stream(req.image.buffer.clone())
-            .file_name(req.image.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-
-        let mut form = Form::new().part("image", file_part);
+        let mut form = FormData::new().file("image", &req.image);
 
         if let Some(n) = req.n {
             form = form.text("n", n.to_string());
@@ -94,6 +84,8 @@ impl<'a> Images<'a> {
             form = form.text("user", user);
         }
 
+        form = form.extra(&req.extra);
+
         self.openai.post_form("/images/variations", form).await
     }
 }
@@ -6,13 +6,16 @@ use crate::client::OpenAI;
 use crate::interfaces::messages;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Messages<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Messages {
+    openai: OpenAI,
 }
 
-impl<'a> Messages<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Messages {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Create a message.
@@ -5,13 +5,16 @@ use crate::client::OpenAI;
 use crate::interfaces::models;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Models<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Models {
+    openai: OpenAI,
 }
 
-impl<'a> Models<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Models {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Retrieves a model instance, providing basic information about the model such as the owner and permissioning.
@@ -4,13 +4,16 @@ use crate::client::OpenAI;
 use crate::interfaces::moderations;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Moderations<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Moderations {
+    openai: OpenAI,
 }
 
-impl<'a> Moderations<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Moderations {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Classifies if text and/or image inputs are potentially harmful. Learn more in the [moderation guide](https://platform.openai.com/docs/guides/moderation).
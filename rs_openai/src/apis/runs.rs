@@ -6,13 +6,16 @@ use crate::client::OpenAI;
 use crate::interfaces::runs;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Runs<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Runs {
+    openai: OpenAI,
 }
 
-impl<'a> Runs<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Runs {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Create a run.
@@ -30,28 +33,48 @@ impl<'a> Runs<'a> {
             .await
     }
 
-    /// Retrieves a thread.
+    /// Retrieves a run.
+    ///
+    /// # Path parameters
+    ///
+    /// - `thread_id` - The ID of the [thread](https://platform.openai.com/docs/api-reference/threads) that was run.
+    /// - `run_id` - The ID of the run to retrieve.
     pub async fn retrieve(
         &self,
-        thread_id: &str, // The thread object matching the specified ID.
+        thread_id: &str,
+        run_id: &str,
     ) -> OpenAIResponse<runs::RunResponse> {
-        self.openai.get(&format!("/runs/{thread_id}"), &()).await
+        self.openai
+            .get(&format!("/threads/{thread_id}/runs/{run_id}"), &())
+            .await
     }
 
-    /// Modifies a thread.
+    /// Modifies a run.
+    ///
+    /// # Path parameters
+    ///
+    /// - `thread_id` - The ID of the [thread](https://platform.openai.com/docs/api-reference/threads) that was run.
+    /// - `run_id` - The ID of the run to modify.
     pub async fn modify(
         &self,
-        thread_id: &str, // The ID of the thread to modify. Only the `metadata` can be modified.
+        thread_id: &str,
+        run_id: &str,
         req: &runs::ModifyRunRequest,
     ) -> OpenAIResponse<runs::RunResponse> {
-        self.openai.post(&format!("/runs/{thread_id}"), req).await
+        self.openai
+            .post(&format!("/threads/{thread_id}/runs/{run_id}"), req)
+            .await
     }
 
-    /// Delete a thread.
-    pub async fn delete(
-        &self,
-        thread_id: &str, // The ID of the thread to modify. Only the `metadata` can be modified.
-    ) -> OpenAIResponse<runs::DeleteRunResponse> {
-        self.openai.delete(&format!("/runs/{thread_id}"), &()).await
+    /// Cancels a run that is `in_progress`.
+    ///
+    /// # Path parameters
+    ///
+    /// - `thread_id` - The ID of the thread to which this run belongs.
+    /// - `run_id` - The ID of the run to cancel.
+    pub async fn cancel(&self, thread_id: &str, run_id: &str) -> OpenAIResponse<runs::RunResponse> {
+        self.openai
+            .post(&format!("/threads/{thread_id}/runs/{run_id}/cancel"), &())
+            .await
     }
 }
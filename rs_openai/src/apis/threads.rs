@@ -6,13 +6,16 @@ use crate::client::OpenAI;
 use crate::interfaces::threads;
 use crate::shared::response_wrapper::OpenAIResponse;
 
-pub struct Threads<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Threads {
+    openai: OpenAI,
 }
 
-impl<'a> Threads<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Threads {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Create a thread.
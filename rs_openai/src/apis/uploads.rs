@@ -4,15 +4,18 @@
 use crate::client::OpenAI;
 use crate::interfaces::uploads;
 use crate::shared::response_wrapper::OpenAIResponse;
-use reqwest::multipart::Form;
+use crate::shared::types::FormData;
 
-pub struct Upload<'a> {
-    openai: &'a OpenAI,
+#[derive(Clone)]
+pub struct Upload {
+    openai: OpenAI,
 }
 
-impl<'a> Upload<'a> {
-    pub fn new(openai: &'a OpenAI) -> Self {
-        Self { openai }
+impl Upload {
+    pub fn new(openai: &OpenAI) -> Self {
+        Self {
+            openai: openai.clone(),
+        }
     }
 
     /// Creates an intermediate [Upload](https://platform.openai.com/docs/api-reference/uploads/object) object that you can add Parts to. Currently,
@@ -30,14 +33,10 @@ impl<'a> Upload<'a> {
         &self,
         req: &uploads::UploadFileRequest,
     ) -> OpenAIResponse<uploads::UploadFileResponse> {
-        let file_part = reqwest::multipart::Part::stream(req.file.buffer.clone())
-            .file_name(req.file.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-
-        let form = Form::new()
-            .part("file", file_part)
-            .text("purpose", req.purpose.to_string());
+        let form = FormData::new()
+            .file("file", &req.file)
+            .text("purpose", req.purpose.to_string())
+            .extra(&req.extra);
 
         self.openai.post_form("/uploads", form).await
     }
@@ -52,11 +51,7 @@ impl<'a> Upload<'a> {
         upload_id: &str,
         req: &uploads::AddUploadPartRequest,
     ) -> OpenAIResponse<uploads::AddUploadPartResponse> {
-        let file_part = reqwest::multipart::Part::stream(req.data.buffer.clone())
-            .file_name(req.data.filename.clone())
-            .mime_str("application/octet-stream")
-            .unwrap();
-        let form = Form::new().part("data", file_part);
+        let form = FormData::new().file("data", &req.data).extra(&req.extra);
 
         self.openai
             .post_form(&format!("/uploads/{upload_id}/parts"), form)
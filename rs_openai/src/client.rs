@@ -1,14 +1,26 @@
 use crate::apis::{
-    audio, chat, completions, edits, embeddings, engines, files, fine_tuning, images, models,
-    moderations,
+    assistants, audio, batch, chat, completions, edits, embeddings, engines, files, fine_tuning,
+    images, messages, models, moderations, runs, threads, uploads,
+};
+use crate::config::{AzureAuth, OpenAIConfig};
+use crate::meta;
+use crate::options::RequestOptions;
+use crate::rate_limit::{estimate_tokens, RateLimitInfo, RateLimiter};
+use crate::retry::{is_idempotent, is_retryable_error, is_retryable_status, retry_after, Attempt};
+use crate::shared::response_wrapper::{ApiError, OpenAIError, OpenAIResponse};
+use crate::shared::types::FormData;
+use crate::sse::EventStream;
+use crate::transport::{HttpResponse, HttpTransport, OpenAIRequest, RequestBody, ReqwestTransport};
+use bytes::Bytes;
+use futures::Stream;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT},
+    Client, Method, Url,
 };
-use crate::shared::response_wrapper::{ApiErrorResponse, OpenAIError, OpenAIResponse};
-use futures::{stream::StreamExt, Stream};
-use reqwest::{header::HeaderMap, multipart::Form, Client, Method, RequestBuilder};
-use reqwest_eventsource::{Event, EventSource, RequestBuilderExt};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fs::File;
 use std::io::{self};
+use std::sync::{Arc, Mutex};
 use std::{fmt::Debug, pin::Pin};
 
 // Default v1 API base url
@@ -17,94 +29,386 @@ pub const API_BASE: &str = "https://api.openai.com/v1";
 /// Name for organization header
 pub const ORGANIZATION_HEADER: &str = "OpenAI-Organization";
 
+/// Name for project header
+pub const PROJECT_HEADER: &str = "OpenAI-Project";
+
+/// Name for the header opting into beta APIs
+pub const BETA_HEADER: &str = "OpenAI-Beta";
+
+/// The `OpenAI-Beta` value required by assistants, threads, messages and runs
+pub const ASSISTANTS_BETA: &str = "assistants=v2";
+
+/// Name for the header making a request safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Name for the Azure OpenAI api key header
+pub const API_KEY_HEADER: &str = "api-key";
+
+/// Name for the Azure OpenAI api version query parameter
+pub const API_VERSION_QUERY: &str = "api-version";
+
+/// The client is a cheap handle to shared state, so clone it into `tokio::spawn`ed tasks or web framework state as needed,
+/// all clones share the same connection pool and rate limiter.
+#[derive(Clone)]
 pub struct OpenAI {
-    pub api_key: String,
-    pub org_id: Option<String>,
+    inner: Arc<OpenAIInner>,
+    options: Arc<RequestOptions>,
+    /// The `idempotency_key` of the options, taken by the first call.
+    idempotency_key: Arc<Mutex<Option<String>>>,
+}
+
+struct OpenAIInner {
+    config: OpenAIConfig,
+    transport: Box<dyn HttpTransport>,
+    rate_limit_info: Mutex<Option<RateLimitInfo>>,
 }
 
 impl OpenAI {
-    pub fn new(&self) -> Self {
+    pub fn new(config: OpenAIConfig) -> Self {
+        let transport = ReqwestTransport::from_config(&config);
+        Self::with_transport(config, transport)
+    }
+
+    /// Creates a client configured from the `OPENAI_*` environment variables, see [`OpenAIConfig::from_env`].
+    ///
+    /// Fails with [`OpenAIError::MissingEnvVar`] when `OPENAI_API_KEY` is not set.
+    pub fn from_env() -> OpenAIResponse<Self> {
+        Ok(Self::new(OpenAIConfig::from_env()?))
+    }
+
+    /// Same as [`OpenAI::from_env`], but loads a `.env` file from the current directory or its parents first.
+    ///
+    /// Variables already set in the environment take precedence over the file, and a missing file is not an error.
+    pub fn from_dotenv() -> OpenAIResponse<Self> {
+        dotenvy::dotenv().ok();
+        Self::from_env()
+    }
+
+    /// Creates a client on top of an already configured `reqwest::Client`, e.g. one shared with the rest of your application.
+    ///
+    /// The connection pool, proxy, TLS and `connect_timeout`/`read_timeout` settings are taken from `http_client`,
+    /// everything else still comes from `config`.
+    pub fn with_http_client(config: OpenAIConfig, http_client: Client) -> Self {
+        Self::with_transport(config, ReqwestTransport::new(http_client))
+    }
+
+    /// Creates a client that sends every request through `transport`, e.g. an in-memory fake in tests.
+    ///
+    /// `connect_timeout` and `read_timeout` are up to the transport, everything else still comes from `config`.
+    pub fn with_transport<T>(config: OpenAIConfig, transport: T) -> Self
+    where
+        T: HttpTransport,
+    {
         Self {
-            api_key: self.api_key.to_owned(),
-            org_id: self.org_id.to_owned(),
+            inner: Arc::new(OpenAIInner {
+                config,
+                transport: Box::new(transport),
+                rate_limit_info: Mutex::new(None),
+            }),
+            options: Arc::default(),
+            idempotency_key: Arc::default(),
         }
     }
 
-    fn headers(&self) -> HeaderMap {
-        let mut headers = HeaderMap::new();
+    /// Creates a client that sends every request through a tower service stack, see [`service`](crate::service).
+    #[cfg(feature = "tower")]
+    pub fn from_service<S>(config: OpenAIConfig, service: S) -> Self
+    where
+        S: tower::Service<OpenAIRequest, Response = HttpResponse> + Clone + Send + 'static,
+        S::Error: Into<tower::BoxError>,
+        S::Future: Send,
+    {
+        Self::with_transport(config, crate::service::ServiceTransport::new(service))
+    }
+
+    /// Returns a handle whose requests carry `options`, replacing the ones of `self`, see [`options`](crate::options).
+    ///
+    /// The handle shares the connection pool and retry policy of `self`, and its rate limiter unless `options` set another api key.
+    /// The `idempotency_key` is sent with the first call through the handle only.
+    pub fn with_options(&self, mut options: RequestOptions) -> Self {
+        let idempotency_key = options.idempotency_key.take();
 
-        if let Some(org_id) = &self.org_id {
-            headers.insert(ORGANIZATION_HEADER, org_id.parse().unwrap());
+        Self {
+            inner: self.inner.clone(),
+            options: Arc::new(options),
+            idempotency_key: Arc::new(Mutex::new(idempotency_key)),
         }
+    }
 
-        headers
+    pub fn config(&self) -> &OpenAIConfig {
+        &self.inner.config
     }
 
-    fn openai_request<F>(&self, method: Method, route: &str, builder: F) -> RequestBuilder
-    where
-        F: FnOnce(RequestBuilder) -> RequestBuilder,
-    {
-        let client = Client::new();
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
 
-        let mut request = client
-            .request(method, API_BASE.to_string() + route)
-            .headers(self.headers())
-            .bearer_auth(&self.api_key);
+    /// The `x-ratelimit-*` headers of the latest response that carried them, for the api key of the config.
+    ///
+    /// With concurrent requests this is whichever response came in last, so treat it as a hint.
+    /// The rate limits of a specific response are on its [`ResponseMeta::rate_limit`](crate::ResponseMeta::rate_limit)
+    /// and [`ApiError::rate_limit`](crate::shared::response_wrapper::ApiError::rate_limit).
+    pub fn rate_limit_info(&self) -> Option<RateLimitInfo> {
+        self.inner.rate_limit_info.lock().unwrap().clone()
+    }
 
-        request = builder(request);
-        request
+    fn headers(&self, route: &str) -> OpenAIResponse<HeaderMap> {
+        let mut headers = HeaderMap::new();
+
+        if route.starts_with("/assistants") || route.starts_with("/threads") {
+            headers.insert(BETA_HEADER, HeaderValue::from_static(ASSISTANTS_BETA));
+        }
+
+        let config = &self.inner.config;
+        let options = &self.options;
+
+        if let Some(org_id) = options.org_id.as_ref().or(config.org_id.as_ref()) {
+            headers.insert(ORGANIZATION_HEADER, header_value("org id", org_id)?);
+        }
+
+        if let Some(project_id) = options.project_id.as_ref().or(config.project_id.as_ref()) {
+            headers.insert(PROJECT_HEADER, header_value("project id", project_id)?);
+        }
+
+        if let Some(user_agent) = &self.inner.config.user_agent {
+            headers.insert(USER_AGENT, header_value("user agent", user_agent)?);
+        }
+
+        if let Some(idempotency_key) = self.idempotency_key.lock().unwrap().take() {
+            headers.insert(
+                IDEMPOTENCY_KEY_HEADER,
+                header_value("idempotency key", &idempotency_key)?,
+            );
+        }
+
+        headers.extend(config.headers.clone());
+        headers.extend(options.headers.clone());
+        Ok(headers)
     }
 
-    async fn resolve_response<T>(request: RequestBuilder) -> OpenAIResponse<T>
+    fn url(&self, route: &str, body: &RequestBody) -> OpenAIResponse<String> {
+        let base_url = self.inner.config.base_url.trim_end_matches('/');
+
+        Ok(match &self.inner.config.azure {
+            Some(azure) if azure.is_deployment_route(route) => {
+                let deployment = match azure.deployment_id.as_str() {
+                    "" => body.model().ok_or_else(|| {
+                        OpenAIError::InvalidArgument(
+                            "azure requests need a deployment_id or a model".into(),
+                        )
+                    })?,
+                    deployment_id => deployment_id.to_owned(),
+                };
+
+                format!("{base_url}/openai/deployments/{deployment}{route}")
+            }
+            Some(_) => format!("{base_url}/openai{route}"),
+            None => format!("{base_url}{route}"),
+        })
+    }
+
+    fn openai_request<Q>(
+        &self,
+        method: Method,
+        route: &str,
+        query: &Q,
+        body: RequestBody,
+    ) -> OpenAIResponse<OpenAIRequest>
     where
-        T: DeserializeOwned + Debug,
+        Q: Serialize + ?Sized,
     {
-        let response = request.send().await?;
-        let status = response.status();
-        let bytes = response.bytes().await?;
+        let mut url = Url::parse(&self.url(route, &body)?)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid url: {e}")))?;
+        let query = serde_urlencoded::to_string(query)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid query: {e}")))?;
 
-        if !status.is_success() {
-            let api_error: ApiErrorResponse =
-                serde_json::from_slice(bytes.as_ref()).map_err(OpenAIError::JSONDeserialize)?;
+        if !query.is_empty() {
+            url.set_query(Some(&query));
+        }
 
-            return Err(OpenAIError::ApiError(api_error));
+        if !self.options.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.options.query);
         }
 
-        let data: T =
-            serde_json::from_slice(bytes.as_ref()).map_err(OpenAIError::JSONDeserialize)?;
+        let mut headers = self.headers(route)?;
+        let api_key = self
+            .options
+            .api_key
+            .as_ref()
+            .unwrap_or(&self.inner.config.api_key);
+
+        let (name, value) = match &self.inner.config.azure {
+            Some(azure) => {
+                url.query_pairs_mut()
+                    .append_pair(API_VERSION_QUERY, &azure.api_version);
+
+                match azure.auth {
+                    AzureAuth::ApiKey => (HeaderName::from_static(API_KEY_HEADER), api_key.clone()),
+                    AzureAuth::EntraId => (AUTHORIZATION, format!("Bearer {api_key}")),
+                }
+            }
+            None => (AUTHORIZATION, format!("Bearer {api_key}")),
+        };
 
-        Ok(data)
+        let mut value = header_value("api key", &value)?;
+        value.set_sensitive(true);
+        headers.insert(name, value);
+
+        if let RequestBody::Json(_) = body {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
+
+        Ok(OpenAIRequest {
+            method,
+            url,
+            headers,
+            body,
+            timeout: self.options.timeout.or(self.inner.config.timeout),
+        })
     }
 
-    async fn resolve_text_response(request: RequestBuilder) -> OpenAIResponse<String> {
-        let response = request.send().await?;
-        let status = response.status();
-        let text = response.text().await?;
+    /// Sends the request, retrying it according to the configured [`RetryPolicy`](crate::retry::RetryPolicy).
+    async fn send(&self, request: OpenAIRequest) -> OpenAIResponse<HttpResponse> {
+        let tokens = self.estimate_tokens(&request.body);
+        let idempotent = is_idempotent(&request);
+        let retry_policy = &self.inner.config.retry_policy;
+
+        // Only attempts that may be followed by another one send a clone, the last one sends the request itself.
+        let mut attempts = if retry_policy.allows(idempotent) {
+            retry_policy.max_attempts
+        } else {
+            1
+        };
+        let mut request = Some(request);
+
+        retry_policy
+            .run(idempotent, || {
+                attempts = attempts.saturating_sub(1);
+                let request = match attempts {
+                    0 => request.take(),
+                    _ => request.clone(),
+                }
+                .expect("no attempt after the last one");
+
+                async move {
+                    self.acquire(tokens).await;
+
+                    let response = self.inner.transport.send(request).await;
+                    if let Ok(response) = &response {
+                        self.observe(&response.headers);
+                        meta::record(response.status, &response.headers);
+                    }
 
-        if !status.is_success() {
-            let api_error: ApiErrorResponse =
-                serde_json::from_slice(text.as_ref()).map_err(OpenAIError::JSONDeserialize)?;
+                    match response {
+                        Ok(response) if is_retryable_status(response.status) => {
+                            // The error object tells a rate limit from a used up quota, both are a `429`.
+                            let delay = retry_after(&response.headers);
+                            let error = Self::read_error(response).await;
 
-            return Err(OpenAIError::ApiError(api_error));
+                            if error.is_retryable() {
+                                Attempt::Retry(Err(error), delay)
+                            } else {
+                                Attempt::Done(Err(error))
+                            }
+                        }
+                        Ok(response) => Attempt::Done(Ok(response)),
+                        Err(e) if is_retryable_error(&e) => Attempt::Retry(Err(e), None),
+                        Err(e) => Attempt::Done(Err(e)),
+                    }
+                }
+            })
+            .await
+    }
+
+    /// The [`RateLimiter`](crate::rate_limit::RateLimiter) for the api key of the requests, if any.
+    fn rate_limiter(&self) -> Option<&RateLimiter> {
+        match &self.options.rate_limiter {
+            Some(rate_limiter) => Some(rate_limiter),
+            None if self.options.api_key.is_none() => self.inner.config.rate_limiter.as_ref(),
+            None => None,
         }
+    }
 
-        Ok(text)
+    /// Waits for the [`RateLimiter`](crate::rate_limit::RateLimiter), if any.
+    async fn acquire(&self, tokens: u32) {
+        if let Some(rate_limiter) = self.rate_limiter() {
+            rate_limiter.acquire(tokens).await;
+        }
     }
 
-    async fn resolve_file_response(request: RequestBuilder, filename: &str) -> OpenAIResponse<()> {
-        let response = request.send().await?;
-        let status = response.status();
-        let text = response.text().await?;
+    /// Records the `x-ratelimit-*` headers of a response.
+    fn observe(&self, headers: &HeaderMap) {
+        if let Some(info) = RateLimitInfo::from_headers(headers) {
+            if let Some(rate_limiter) = self.rate_limiter() {
+                rate_limiter.observe(&info);
+            }
 
-        if !status.is_success() {
-            let api_error: ApiErrorResponse =
-                serde_json::from_slice(text.as_ref()).map_err(OpenAIError::JSONDeserialize)?;
+            if self.options.api_key.is_none() {
+                *self.inner.rate_limit_info.lock().unwrap() = Some(info);
+            }
+        }
+    }
 
-            return Err(OpenAIError::ApiError(api_error));
+    /// Estimates the tokens a JSON body costs for the [`RateLimiter`](crate::rate_limit::RateLimiter).
+    fn estimate_tokens(&self, body: &RequestBody) -> u32 {
+        match body {
+            RequestBody::Json(body) => estimate_tokens(body.len()),
+            _ => 0,
         }
+    }
+
+    fn json<F>(json: &F) -> OpenAIResponse<RequestBody>
+    where
+        F: Serialize + ?Sized,
+    {
+        serde_json::to_vec(json)
+            .map(|body| RequestBody::Json(body.into()))
+            .map_err(|e| OpenAIError::InvalidArgument(format!("failed to serialize request: {e}")))
+    }
+
+    /// Reads the error object of a failed response.
+    async fn read_error(response: HttpResponse) -> OpenAIError {
+        let status = response.status;
+        let headers = response.headers.clone();
+
+        match response.bytes().await {
+            Ok(body) => OpenAIError::ApiError(Box::new(ApiError::new(status, &headers, &body))),
+            Err(e) => e,
+        }
+    }
+
+    /// Reads the whole body of a successful response, or the error object of a failed one.
+    async fn read_body(response: HttpResponse) -> OpenAIResponse<Bytes> {
+        if !response.status.is_success() {
+            return Err(Self::read_error(response).await);
+        }
+
+        response.bytes().await
+    }
+
+    async fn resolve_response<T>(response: HttpResponse) -> OpenAIResponse<T>
+    where
+        T: DeserializeOwned + Debug,
+    {
+        let bytes = Self::read_body(response).await?;
+
+        let data: T =
+            serde_json::from_slice(bytes.as_ref()).map_err(OpenAIError::JSONDeserialize)?;
+
+        Ok(data)
+    }
+
+    async fn resolve_text_response(response: HttpResponse) -> OpenAIResponse<String> {
+        let bytes = Self::read_body(response).await?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn resolve_file_response(response: HttpResponse, filename: &str) -> OpenAIResponse<()> {
+        let bytes = Self::read_body(response).await?;
 
         let mut file = File::create(filename).expect("failed to create file");
-        io::copy(&mut text.as_bytes(), &mut file).expect("failed to copy content");
+        io::copy(&mut bytes.as_ref(), &mut file).expect("failed to copy content");
 
         Ok(())
     }
@@ -114,52 +418,40 @@ impl OpenAI {
         T: DeserializeOwned + Debug,
         F: Serialize,
     {
-        let request = self.openai_request(Method::GET, route, |request| request.query(query));
-        Self::resolve_response(request).await
-    }
-
-    // pub(crate) async fn get_stream<T, F>(
-    //     &self,
-    //     route: &str,
-    //     query: &F,
-    // ) -> Pin<Box<dyn Stream<Item = OpenAIResponse<T>> + Send>>
-    // where
-    //     T: DeserializeOwned + Debug + Send + 'static,
-    //     F: Serialize,
-    // {
-    //     let event_source = self
-    //         .openai_request(Method::GET, route, |request| request.query(query))
-    //         .eventsource()
-    //         .unwrap();
-    //     Self::stream_sse(event_source).await
-    // }
+        let request = self.openai_request(Method::GET, route, query, RequestBody::Empty)?;
+        let response = self.send(request).await?;
+        Self::resolve_response(response).await
+    }
 
     pub(crate) async fn post<T, F>(&self, route: &str, json: &F) -> OpenAIResponse<T>
     where
         T: DeserializeOwned + Debug,
         F: Serialize,
     {
-        let request = self.openai_request(Method::POST, route, |request| request.json(json));
-        Self::resolve_response(request).await
+        let request = self.openai_request(Method::POST, route, &(), Self::json(json)?)?;
+        let response = self.send(request).await?;
+        Self::resolve_response(response).await
     }
 
-    pub(crate) async fn post_form<T>(&self, route: &str, form_data: Form) -> OpenAIResponse<T>
+    pub(crate) async fn post_form<T>(&self, route: &str, form_data: FormData) -> OpenAIResponse<T>
     where
         T: DeserializeOwned + Debug,
     {
         let request =
-            self.openai_request(Method::POST, route, |request| request.multipart(form_data));
-        Self::resolve_response(request).await
+            self.openai_request(Method::POST, route, &(), RequestBody::Multipart(form_data))?;
+        let response = self.send(request).await?;
+        Self::resolve_response(response).await
     }
 
     pub(crate) async fn post_form_with_text_response(
         &self,
         route: &str,
-        form_data: Form,
+        form_data: FormData,
     ) -> OpenAIResponse<String> {
         let request =
-            self.openai_request(Method::POST, route, |request| request.multipart(form_data));
-        Self::resolve_text_response(request).await
+            self.openai_request(Method::POST, route, &(), RequestBody::Multipart(form_data))?;
+        let response = self.send(request).await?;
+        Self::resolve_text_response(response).await
     }
 
     pub(crate) async fn post_with_file_response<T>(
@@ -171,24 +463,21 @@ impl OpenAI {
     where
         T: Serialize,
     {
-        let request = self.openai_request(Method::POST, route, |request| request.json(json));
-        Self::resolve_file_response(request, filename).await
+        let request = self.openai_request(Method::POST, route, &(), Self::json(json)?)?;
+        let response = self.send(request).await?;
+        Self::resolve_file_response(response, filename).await
     }
 
     pub(crate) async fn post_stream<T, F>(
         &self,
         route: &str,
         json: &F,
-    ) -> Pin<Box<dyn Stream<Item = OpenAIResponse<T>> + Send>>
+    ) -> OpenAIResponse<Pin<Box<dyn Stream<Item = OpenAIResponse<T>> + Send>>>
     where
         T: DeserializeOwned + Debug + Send + 'static,
         F: Serialize,
     {
-        let event_source = self
-            .openai_request(Method::POST, route, |request| request.json(json))
-            .eventsource()
-            .unwrap();
-        OpenAI::stream_sse(event_source).await
+        Ok(self.post_events(route, json).await?.decode())
     }
 
     pub(crate) async fn delete<T, F>(&self, route: &str, json: &F) -> OpenAIResponse<T>
@@ -196,59 +485,63 @@ impl OpenAI {
         T: DeserializeOwned + Debug,
         F: Serialize,
     {
-        let request = self.openai_request(Method::DELETE, route, |request| request.json(json));
-        Self::resolve_response(request).await
+        let request = self.openai_request(Method::DELETE, route, &(), Self::json(json)?)?;
+        let response = self.send(request).await?;
+        Self::resolve_response(response).await
     }
 
-    async fn stream_sse<T>(
-        mut event_source: EventSource,
-    ) -> Pin<Box<dyn Stream<Item = OpenAIResponse<T>> + Send>>
+    /// Sends a `GET` request to `route`, e.g. `/fine_tuning/jobs/{id}/events`, and returns the server-sent events of the response as they arrive.
+    ///
+    /// Fails with [`OpenAIError::ApiError`] if the server rejected the request, see [`sse`](crate::sse).
+    pub async fn get_events<Q>(&self, route: &str, query: &Q) -> OpenAIResponse<EventStream>
     where
-        T: DeserializeOwned + Debug + Send + 'static,
+        Q: Serialize + ?Sized,
     {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<OpenAIResponse<T>>();
-
-        tokio::spawn(async move {
-            while let Some(evt) = event_source.next().await {
-                match evt {
-                    Err(e) => {
-                        if tx
-                            .send(Err(OpenAIError::StreamError(e.to_string())))
-                            .is_err()
-                        {
-                            break;
-                        }
-                    }
-                    Ok(evt) => match evt {
-                        Event::Message(message) => {
-                            if message.data == "[DONE]" {
-                                break;
-                            }
+        let request = self.openai_request(Method::GET, route, query, RequestBody::Empty)?;
+        self.open_stream(request).await
+    }
 
-                            let response = match serde_json::from_str::<T>(&message.data) {
-                                Err(e) => Err(OpenAIError::JSONDeserialize(e)),
-                                Ok(output) => Ok(output),
-                            };
+    /// Sends a `POST` request with `json` as the body to `route`, e.g. `/threads/{id}/runs` with `stream` set,
+    /// and returns the server-sent events of the response as they arrive.
+    ///
+    /// Fails with [`OpenAIError::ApiError`] if the server rejected the request, see [`sse`](crate::sse).
+    pub async fn post_events<F>(&self, route: &str, json: &F) -> OpenAIResponse<EventStream>
+    where
+        F: Serialize + ?Sized,
+    {
+        let request = self.openai_request(Method::POST, route, &(), Self::json(json)?)?;
+        self.open_stream(request).await
+    }
 
-                            if tx.send(response).is_err() {
-                                break;
-                            }
-                        }
-                        Event::Open => continue,
-                    },
-                }
-            }
+    /// Opens an event stream, retrying it according to the configured [`RetryPolicy`](crate::retry::RetryPolicy)
+    /// until the server accepted it.
+    ///
+    /// Once open, the stream is never reconnected, since the endpoints cannot resume a response half way.
+    async fn open_stream(&self, mut request: OpenAIRequest) -> OpenAIResponse<EventStream> {
+        request
+            .headers
+            .insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+
+        let response = self.send(request).await?;
+        if !response.status.is_success() {
+            return Err(Self::read_error(response).await);
+        }
 
-            event_source.close();
-        });
+        Ok(EventStream::new(response))
+    }
 
-        Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
+    pub fn assistants(&self) -> assistants::Assistants {
+        assistants::Assistants::new(self)
     }
 
     pub fn audio(&self) -> audio::Audio {
         audio::Audio::new(self)
     }
 
+    pub fn batches(&self) -> batch::Batch {
+        batch::Batch::new(self)
+    }
+
     pub fn chat(&self) -> chat::Chat {
         chat::Chat::new(self)
     }
@@ -281,6 +574,10 @@ impl OpenAI {
         images::Images::new(self)
     }
 
+    pub fn messages(&self) -> messages::Messages {
+        messages::Messages::new(self)
+    }
+
     pub fn models(&self) -> models::Models {
         models::Models::new(self)
     }
@@ -288,4 +585,300 @@ impl OpenAI {
     pub fn moderations(&self) -> moderations::Moderations {
         moderations::Moderations::new(self)
     }
+
+    pub fn runs(&self) -> runs::Runs {
+        runs::Runs::new(self)
+    }
+
+    pub fn threads(&self) -> threads::Threads {
+        threads::Threads::new(self)
+    }
+
+    pub fn uploads(&self) -> uploads::Upload {
+        uploads::Upload::new(self)
+    }
+}
+
+/// A header value taken from the config or the options, which may contain anything.
+pub(crate) fn header_value(name: &str, value: &str) -> OpenAIResponse<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|e| OpenAIError::InvalidArgument(format!("invalid {name}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OpenAIConfigBuilder;
+    use crate::options::RequestOptionsBuilder;
+    use crate::transport::fake::{client, Fake};
+    use reqwest::StatusCode;
+    async fn get(client: &OpenAI) -> OpenAIResponse<serde_json::Value> {
+        client.get("/models", &()).await
+    }
+
+    #[tokio::test]
+    async fn invalid_option_headers_are_an_error() {
+        let fake = Fake::default();
+        let options = RequestOptionsBuilder::default()
+            .idempotency_key("a\nb")
+            .build()
+            .unwrap();
+
+        let error = get(&client(&fake).with_options(options)).await.unwrap_err();
+
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+        assert!(fake.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn options_override_the_config() {
+        let fake = Fake::default().respond(200, "{}");
+        let options = RequestOptionsBuilder::default()
+            .api_key("sk-tenant")
+            .project_id("proj_tenant")
+            .query(vec![("limit".to_owned(), "1".to_owned())])
+            .build()
+            .unwrap();
+
+        get(&client(&fake).with_options(options)).await.unwrap();
+
+        let request = &fake.requests()[0];
+        assert_eq!(request.headers[AUTHORIZATION], "Bearer sk-tenant");
+        assert_eq!(request.headers[PROJECT_HEADER], "proj_tenant");
+        assert_eq!(request.url.query(), Some("limit=1"));
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_is_sent_with_the_first_call_only() {
+        let fake = Fake::default().respond(500, "{}").respond(200, "{}");
+        let options = RequestOptionsBuilder::default()
+            .idempotency_key("order-42")
+            .build()
+            .unwrap();
+        let client = client(&fake).with_options(options);
+        let post = |client: OpenAI| async move {
+            client
+                .post::<serde_json::Value, _>("/embeddings", &())
+                .await
+        };
+
+        post(client.clone()).await.unwrap();
+        post(client.clone()).await.unwrap();
+
+        let keys: Vec<_> = fake
+            .requests()
+            .iter()
+            .map(|request| request.headers.get(IDEMPOTENCY_KEY_HEADER).cloned())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                Some("order-42".parse().unwrap()),
+                Some("order-42".parse().unwrap()),
+                None
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn another_api_key_does_not_feed_the_shared_rate_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining-requests", "0".parse().unwrap());
+        let fake = Fake::default().respond_with_headers(200, headers, "{}");
+        let options = RequestOptionsBuilder::default()
+            .api_key("sk-tenant")
+            .build()
+            .unwrap();
+        let client = client(&fake);
+
+        get(&client.with_options(options)).await.unwrap();
+
+        assert_eq!(client.rate_limit_info(), None);
+    }
+
+    #[tokio::test]
+    async fn rate_limits_are_retried() {
+        let rate_limited =
+            r#"{"error":{"message":"slow down","type":"requests","code":"rate_limit_exceeded"}}"#;
+        let fake = Fake::default()
+            .respond(429, rate_limited)
+            .respond(200, "{}");
+
+        get(&client(&fake)).await.unwrap();
+
+        assert_eq!(fake.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn used_up_quota_is_not_retried() {
+        let no_quota = r#"{"error":{"message":"no quota","type":"insufficient_quota","code":"insufficient_quota"}}"#;
+        let fake = Fake::default().respond(429, no_quota).respond(200, "{}");
+
+        let error = get(&client(&fake)).await.unwrap_err();
+
+        assert_eq!(fake.requests().len(), 1);
+        assert_eq!(
+            error.api_error().and_then(ApiError::code),
+            Some(crate::shared::response_wrapper::ApiErrorCode::InsufficientQuota)
+        );
+    }
+
+    fn azure_client(fake: &Fake, deployment_id: &str) -> OpenAI {
+        let azure = crate::config::AzureConfigBuilder::default()
+            .deployment_id(deployment_id)
+            .build()
+            .unwrap();
+        let config = OpenAIConfigBuilder::default()
+            .api_key("azure-key")
+            .base_url("https://my-resource.openai.azure.com/")
+            .azure(azure)
+            .build()
+            .unwrap();
+
+        OpenAI::with_transport(config, fake.clone())
+    }
+
+    #[tokio::test]
+    async fn rate_limits_are_reported_per_response() {
+        use crate::meta::WithRawResponse;
+
+        let rate_limit = |remaining: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "x-ratelimit-remaining-requests",
+                HeaderValue::from_static(remaining),
+            );
+            headers
+        };
+        let fake = Fake::default()
+            .respond_with_headers(200, rate_limit("9"), "{}")
+            .respond_with_headers(400, rate_limit("8"), "{}");
+        let client = client(&fake);
+
+        let res = get(&client).with_raw_response().await.unwrap();
+        let error = get(&client).await.unwrap_err();
+
+        assert_eq!(res.meta.rate_limit.unwrap().remaining_requests, Some(9));
+        let error = error.api_error().unwrap();
+        assert_eq!(
+            error.rate_limit.as_ref().unwrap().remaining_requests,
+            Some(8)
+        );
+    }
+
+    #[tokio::test]
+    async fn azure_uses_the_model_as_the_default_deployment() {
+        let fake = Fake::default();
+        let client = azure_client(&fake, "");
+
+        client
+            .post::<serde_json::Value, _>(
+                "/chat/completions",
+                &serde_json::json!({ "model": "gpt-4o" }),
+            )
+            .await
+            .unwrap();
+        client
+            .post_form::<serde_json::Value>(
+                "/audio/transcriptions",
+                FormData::new().text("model", "whisper-1"),
+            )
+            .await
+            .unwrap();
+
+        let requests = fake.requests();
+        assert_eq!(
+            requests[0].url.path(),
+            "/openai/deployments/gpt-4o/chat/completions"
+        );
+        assert_eq!(
+            requests[1].url.path(),
+            "/openai/deployments/whisper-1/audio/transcriptions"
+        );
+    }
+
+    #[tokio::test]
+    async fn azure_needs_a_deployment_or_a_model() {
+        let fake = Fake::default();
+
+        let error = azure_client(&fake, "")
+            .post::<serde_json::Value, _>("/embeddings", &serde_json::json!({ "input": "hi" }))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+        assert!(fake.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_failing_get_is_retried_until_it_succeeds() {
+        let fake = Fake::default()
+            .respond(503, "unavailable")
+            .respond(500, "{}")
+            .respond(200, r#"{"ok":true}"#);
+
+        let response = get(&client(&fake)).await.unwrap();
+
+        assert_eq!(response, serde_json::json!({ "ok": true }));
+        assert_eq!(fake.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn a_failing_post_is_not_retried() {
+        let fake = Fake::default().respond(500, "{}");
+
+        let error = client(&fake)
+            .post::<serde_json::Value, _>("/chat/completions", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error.api_error().unwrap().status,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(fake.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn azure_routes_models_to_the_deployment() {
+        let fake = Fake::default();
+        let client = azure_client(&fake, "my-gpt");
+
+        client
+            .post::<serde_json::Value, _>("/chat/completions", &serde_json::json!({}))
+            .await
+            .unwrap();
+        client
+            .get::<serde_json::Value, _>("/files", &())
+            .await
+            .unwrap();
+
+        let requests = fake.requests();
+        assert_eq!(
+            requests[0].url.as_str(),
+            "https://my-resource.openai.azure.com/openai/deployments/my-gpt/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(
+            requests[1].url.as_str(),
+            "https://my-resource.openai.azure.com/openai/files?api-version=2024-10-21"
+        );
+        assert_eq!(requests[0].headers[API_KEY_HEADER], "azure-key");
+        assert!(!requests[0].headers.contains_key(AUTHORIZATION));
+    }
+
+    #[tokio::test]
+    async fn an_absurd_retry_after_falls_back_to_the_backoff() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            HeaderValue::from_static("1e30"),
+        );
+        let fake = Fake::default()
+            .respond_with_headers(503, headers, "{}")
+            .respond(200, "{}");
+
+        get(&client(&fake)).await.unwrap();
+
+        assert_eq!(fake.requests().len(), 2);
+    }
 }
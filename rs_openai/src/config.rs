@@ -0,0 +1,375 @@
+//! Client configuration, e.g. the API base url, timeouts and the headers sent with every request.
+//!
+//! ```ignore
+//! use rs_openai::{OpenAI, OpenAIConfigBuilder};
+//! use std::time::Duration;
+//!
+//! let config = OpenAIConfigBuilder::default()
+//!     .api_key("sk-...")
+//!     .base_url("http://localhost:8080/v1")
+//!     .timeout(Duration::from_secs(60))
+//!     .build()?;
+//!
+//! let client = OpenAI::new(config);
+//! ```
+//!
+//! Or read the standard `OPENAI_*` variables with [`OpenAIConfig::from_env`], see [`OpenAI::from_env`](crate::OpenAI::from_env).
+
+use crate::client::{header_value, API_BASE};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use derive_builder::Builder;
+use reqwest::header::HeaderMap;
+use std::fmt::{self, Debug};
+use std::{env, time::Duration};
+
+/// Name for the environment variable holding the api key
+pub const API_KEY_ENV: &str = "OPENAI_API_KEY";
+
+/// Name for the environment variable holding the base url
+pub const BASE_URL_ENV: &str = "OPENAI_BASE_URL";
+
+/// Name for the environment variable holding the organization id
+pub const ORG_ID_ENV: &str = "OPENAI_ORG_ID";
+
+/// Name for the environment variable holding the project id
+pub const PROJECT_ID_ENV: &str = "OPENAI_PROJECT_ID";
+
+/// Name for the environment variable holding the total timeout in seconds
+pub const TIMEOUT_ENV: &str = "OPENAI_TIMEOUT";
+
+/// Name for the environment variable holding the connect timeout in seconds
+pub const CONNECT_TIMEOUT_ENV: &str = "OPENAI_CONNECT_TIMEOUT";
+
+/// Name for the environment variable holding the read timeout in seconds
+pub const READ_TIMEOUT_ENV: &str = "OPENAI_READ_TIMEOUT";
+
+#[derive(Builder, Clone)]
+#[builder(name = "OpenAIConfigBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
+pub struct OpenAIConfig {
+    /// The secret key used for the `Authorization: Bearer` header, which is available on the [website](https://platform.openai.com/account/api-keys).
+    pub api_key: String,
+
+    /// For users who belong to multiple organizations, specifies which organization is used for an API request.
+    /// Usage from these API requests will count against the specified organization's subscription quota.
+    pub org_id: Option<String>,
+
+    /// Specifies which project is used for an API request, sent as the `OpenAI-Project` header.
+    pub project_id: Option<String>,
+
+    /// The base url every route is appended to, e.g. an internal gateway or any OpenAI-compatible server.
+    pub base_url: String, // default: https://api.openai.com/v1
+
+    /// Timeout for the connect phase of a request.
+    pub connect_timeout: Option<Duration>,
+
+    /// Timeout for each read of the response body.
+    pub read_timeout: Option<Duration>,
+
+    /// Total timeout of a request, from when it starts connecting until the response body has finished.
+    pub timeout: Option<Duration>,
+
+    /// Extra headers sent with every request.
+    pub headers: HeaderMap,
+
+    /// Sends every request to an Azure OpenAI resource instead, with `base_url` set to its endpoint, e.g. `https://my-resource.openai.azure.com`.
+    pub azure: Option<AzureConfig>,
+
+    /// The `User-Agent` header sent with every request.
+    pub user_agent: Option<String>,
+
+    /// How requests failing with `429`, `5xx` or a connection error are retried.
+    pub retry_policy: RetryPolicy,
+
+    /// Delays requests on the client side to stay within a requests-per-minute and tokens-per-minute budget.
+    pub rate_limiter: Option<RateLimiter>,
+}
+
+/// How to authenticate against an Azure OpenAI resource, the credential itself is [`OpenAIConfig::api_key`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AzureAuth {
+    /// Sends the key of the resource as the `api-key` header.
+    #[default]
+    ApiKey,
+    /// Sends a Microsoft Entra ID token as the `Authorization: Bearer` header.
+    EntraId,
+}
+
+/// Azure OpenAI serves models from deployments (`/openai/deployments/{deployment_id}/chat/completions`) and needs an `api-version` on every request.
+///
+/// Without a `deployment_id`, every request goes to the deployment named after its `model`,
+/// so one client can use several deployments as long as they are named after their models.
+///
+/// ```ignore
+/// use rs_openai::{AzureConfigBuilder, OpenAI, OpenAIConfigBuilder};
+///
+/// let client = OpenAI::new(
+///     OpenAIConfigBuilder::default()
+///         .api_key("...")
+///         .base_url("https://my-resource.openai.azure.com")
+///         .azure(
+///             AzureConfigBuilder::default()
+///                 .deployment_id("gpt-4o")
+///                 .build()?,
+///         )
+///         .build()?,
+/// );
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(name = "AzureConfigBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct AzureConfig {
+    /// The deployment serving chat, completions, embeddings, images and audio requests, whatever their `model`.
+    /// Leave it empty to use the `model` of each request as the deployment.
+    pub deployment_id: String,
+
+    /// The [API version](https://learn.microsoft.com/en-us/azure/ai-services/openai/reference) sent as the `api-version` query parameter.
+    pub api_version: String, // default: 2024-10-21
+
+    /// Whether `api_key` is the key of the resource or a Microsoft Entra ID token.
+    pub auth: AzureAuth,
+}
+
+impl Default for AzureConfig {
+    fn default() -> Self {
+        Self {
+            deployment_id: "".to_owned(),
+            api_version: "2024-10-21".to_owned(),
+            auth: AzureAuth::default(),
+        }
+    }
+}
+
+impl AzureConfig {
+    /// Model endpoints live under the deployment, everything else (files, batches, ...) under `/openai`.
+    pub(crate) fn is_deployment_route(&self, route: &str) -> bool {
+        [
+            "/chat/completions",
+            "/completions",
+            "/embeddings",
+            "/images/",
+            "/audio/",
+        ]
+        .iter()
+        .any(|prefix| route.starts_with(prefix))
+    }
+}
+
+impl OpenAIConfig {
+    /// Reads the configuration from the environment:
+    ///
+    /// - `OPENAI_API_KEY` (required)
+    /// - `OPENAI_BASE_URL`
+    /// - `OPENAI_ORG_ID`
+    /// - `OPENAI_PROJECT_ID`
+    /// - `OPENAI_TIMEOUT`, `OPENAI_CONNECT_TIMEOUT` and `OPENAI_READ_TIMEOUT`, in seconds, e.g. `30` or `0.5`
+    ///
+    /// Empty variables count as unset, everything not covered here keeps its default.
+    /// Fails if a value cannot be sent as a header, e.g. a key pasted with a line break.
+    pub fn from_env() -> OpenAIResponse<Self> {
+        let api_key = var(API_KEY_ENV).ok_or(OpenAIError::MissingEnvVar(API_KEY_ENV))?;
+        let org_id = var(ORG_ID_ENV);
+        let project_id = var(PROJECT_ID_ENV);
+        let defaults = Self::default();
+
+        validate_headers(
+            Some(&api_key),
+            org_id.as_deref(),
+            project_id.as_deref(),
+            None,
+        )?;
+
+        Ok(Self {
+            api_key,
+            org_id,
+            project_id,
+            base_url: var(BASE_URL_ENV).unwrap_or(defaults.base_url),
+            connect_timeout: seconds(CONNECT_TIMEOUT_ENV)?,
+            read_timeout: seconds(READ_TIMEOUT_ENV)?,
+            timeout: seconds(TIMEOUT_ENV)?,
+            ..defaults
+        })
+    }
+}
+
+impl Debug for OpenAIConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenAIConfig")
+            .field("api_key", &Redacted(&self.api_key))
+            .field("org_id", &self.org_id)
+            .field("project_id", &self.project_id)
+            .field("base_url", &self.base_url)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("timeout", &self.timeout)
+            .field("headers", &self.headers)
+            .field("azure", &self.azure)
+            .field("user_agent", &self.user_agent)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish()
+    }
+}
+
+impl Debug for OpenAIConfigBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenAIConfigBuilder")
+            .field("api_key", &self.api_key.as_deref().map(Redacted))
+            .field("org_id", &self.org_id)
+            .field("project_id", &self.project_id)
+            .field("base_url", &self.base_url)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("timeout", &self.timeout)
+            .field("headers", &self.headers)
+            .field("azure", &self.azure)
+            .field("user_agent", &self.user_agent)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish()
+    }
+}
+
+/// Shows whether a secret such as an api key is set without printing it.
+pub(crate) struct Redacted<'a>(pub(crate) &'a str);
+
+impl Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("\"\"")
+        } else {
+            f.write_str("\"<redacted>\"")
+        }
+    }
+}
+
+impl OpenAIConfigBuilder {
+    fn validate(&self) -> OpenAIResponse<()> {
+        validate_headers(
+            self.api_key.as_deref(),
+            self.org_id.as_ref().and_then(Option::as_deref),
+            self.project_id.as_ref().and_then(Option::as_deref),
+            self.user_agent.as_ref().and_then(Option::as_deref),
+        )
+    }
+}
+
+/// The values sent as headers with every request must not contain line breaks or other control characters.
+fn validate_headers(
+    api_key: Option<&str>,
+    org_id: Option<&str>,
+    project_id: Option<&str>,
+    user_agent: Option<&str>,
+) -> OpenAIResponse<()> {
+    for (name, value) in [
+        ("api key", api_key),
+        ("org id", org_id),
+        ("project id", project_id),
+        ("user agent", user_agent),
+    ] {
+        if let Some(value) = value {
+            header_value(name, value)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+fn seconds(name: &str) -> OpenAIResponse<Option<Duration>> {
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                .ok_or_else(|| {
+                    OpenAIError::InvalidArgument(format!(
+                        "{name} is not a number of seconds: {value}"
+                    ))
+                })
+        })
+        .transpose()
+}
+
+impl Default for OpenAIConfig {
+    fn default() -> Self {
+        Self {
+            api_key: "".to_owned(),
+            org_id: None,
+            project_id: None,
+            base_url: API_BASE.to_owned(),
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            headers: HeaderMap::new(),
+            azure: None,
+            user_agent: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_rejects_values_that_are_no_header_values() {
+        let error = OpenAIConfigBuilder::default()
+            .api_key("sk-test")
+            .project_id("proj_1\r\nX-Injected: 1")
+            .build()
+            .unwrap_err();
+
+        assert!(
+            matches!(error, OpenAIError::InvalidArgument(message) if message.starts_with("invalid project id"))
+        );
+        assert!(OpenAIConfigBuilder::default()
+            .api_key("sk-test\n")
+            .build()
+            .is_err());
+        assert!(OpenAIConfigBuilder::default()
+            .user_agent("bot\0")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn debug_does_not_print_the_api_key() {
+        let mut builder = OpenAIConfigBuilder::default();
+        builder.api_key("sk-secret").org_id("org-1");
+        let config = builder.build().unwrap();
+
+        for debug in [format!("{builder:?}"), format!("{config:?}")] {
+            assert!(!debug.contains("sk-secret"), "{debug}");
+            assert!(debug.contains("<redacted>"), "{debug}");
+            assert!(debug.contains("org-1"), "{debug}");
+        }
+        assert!(format!("{:?}", OpenAIConfig::default()).contains(r#"api_key: """#));
+    }
+
+    #[test]
+    fn build_accepts_header_values() {
+        let config = OpenAIConfigBuilder::default()
+            .api_key("sk-test")
+            .org_id("org-1")
+            .user_agent("my-app/1.0")
+            .build()
+            .unwrap();
+
+        assert_eq!(config.org_id.as_deref(), Some("org-1"));
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -70,6 +71,10 @@ pub struct AssistantRequest {
     /// which indicates the generation exceeded `max_tokens` or the conversation exceeded the max context length.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<ResponseType>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, strum::Display)]
@@ -278,32 +283,32 @@ pub struct JsonSchema {
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct AssistantResponse {
     /// The identifier, which can be referenced in API endpoints.
-    id: String,
+    pub id: String,
     /// The object type, which is always `assistant`.
-    object: String,
+    pub object: String,
     /// The Unix timestamp (in seconds) for when the assistant was created.
-    created_at: u64,
+    pub created_at: u64,
     /// The name of the assistant. The maximum length is 256 characters.
-    name: Option<String>,
+    pub name: Option<String>,
     /// The description of the assistant. The maximum length is 512 characters.
-    description: Option<String>,
+    pub description: Option<String>,
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models,
     /// or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
-    model: String,
+    pub model: String,
     /// The system instructions that the assistant uses. The maximum length is 256,000 characters.
-    instructions: Option<String>,
+    pub instructions: Option<String>,
     /// A list of tool enabled on the assistant. There can be a maximum of 128 tools per assistant. Tools can be of types `code_interpreter`, `file_search`, or `function`.
-    tools: Vec<Tools>,
+    pub tools: Vec<Tools>,
     // A set of resources that are used by the assistant's tools. The resources are specific to the type of tool. For example, the `code_interpreter` tool requires a list of file IDs, while the `file_search` tool requires a list of vector store IDs.
-    tool_resources: Option<ToolResources>,
+    pub tool_resources: Option<ToolResources>,
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
-    metadata: HashMap<String, serde_json::Value>,
+    pub metadata: HashMap<String, serde_json::Value>,
     /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
-    temperature: f64,
+    pub temperature: f64,
     /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass. So 0.1 means only the tokens comprising the top 10% probability mass are considered.
     ///
     /// We generally recommend altering this or temperature but not both.
-    top_p: f64,
+    pub top_p: f64,
     /// Specifies the format that the model must output.
     /// Compatible with [GPT-4o](https://platform.openai.com/docs/models/gpt-4o), [GPT-4 Turbo](https://platform.openai.com/docs/models/gpt-4-turbo-and-gpt-4), and all GPT-3.5 Turbo models since `gpt-3.5-turbo-1106`.
     ///
@@ -317,7 +322,10 @@ pub struct AssistantResponse {
     /// Without this, the model may generate an unending stream of whitespace until the generation reaches the token limit, resulting in a long-running and seemingly "stuck" request.
     /// Also note that the message content may be partially cut off if `finish_reason="length"`,
     /// which indicates the generation exceeded `max_tokens` or the conversation exceeded the max context length.
-    response_format: ResponseType, // "auto" or object
+    pub response_format: ResponseType, // "auto" or object
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -346,12 +354,19 @@ pub struct ListAssistantRequest {
     /// your subsequent call can include before=obj_foo in order to fetch the previous page of the list.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub before: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ListAssistantResponse {
     object: String,
     data: Vec<AssistantResponse>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -359,4 +374,7 @@ pub struct DeleteAssistantResponse {
     id: String,
     object: String,
     delete: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,5 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
-use crate::shared::types::File;
+use crate::shared::types::{Extra, File};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -238,7 +238,8 @@ pub enum Language {
     Sundanese,
 }
 
-#[derive(Debug, Serialize, Default, Clone, strum::Display)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
 pub enum Voice {
     #[default]
     #[strum(serialize = "alloy")]
@@ -253,6 +254,16 @@ pub enum Voice {
     Nova,
     #[strum(serialize = "shimmer")]
     Shimmer,
+    #[strum(serialize = "ash")]
+    Ash,
+    #[strum(serialize = "ballad")]
+    Ballad,
+    #[strum(serialize = "coral")]
+    Coral,
+    #[strum(serialize = "sage")]
+    Sage,
+    #[strum(serialize = "verse")]
+    Verse,
 }
 
 #[derive(Debug, Serialize, Default, Clone, strum::Display)]
@@ -284,7 +295,7 @@ pub struct CreateSpeechRequest {
     /// The text to generate audio for. The maximum length is 4096 characters.
     pub input: String,
 
-    /// The voice to use when generating the audio. Supported voices are `alloy`, `echo`, `fable`, `onyx`, `nova`, and `shimmer`.
+    /// The voice to use when generating the audio. Supported voices are `alloy`, `ash`, `ballad`, `coral`, `echo`, `fable`, `onyx`, `nova`, `sage`, `shimmer`, and `verse`.
     /// Previews of the voices are available in the [Text to speech guide](https://platform.openai.com/docs/guides/text-to-speech/voice-options).
     pub voice: Voice,
 
@@ -295,6 +306,10 @@ pub struct CreateSpeechRequest {
     /// The speed of the generated audio. Select a value from `0.25` to `4.0`. `1.0` is the default.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub speed: Option<f32>, // min: 0.25, max: 4.0, default: 1.0
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -334,6 +349,10 @@ pub struct CreateTranscriptionRequest {
     /// Either or both of these options are supported: `word`, or `segment`. Note: There is no additional latency for segment timestamps, but generating word timestamps incurs additional latency.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp_granularities: Option<Vec<TimestampGranularity>>, // Defaults to segment
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -363,6 +382,10 @@ pub struct CreateTranslationRequest {
     /// If set to 0, the model will use [log probability](https://en.wikipedia.org/wiki/Log_probability) to automatically increase the temperature until certain thresholds are hit.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>, // Defaults to 0
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Default, Clone, strum::Display)]
@@ -389,6 +412,9 @@ pub struct SttResponse {
     pub segments: Option<Vec<Segment>>,
     /// Extracted words and their corresponding timestamps.
     pub words: Option<Vec<Word>>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -413,6 +439,9 @@ pub struct Segment {
     pub compression_ratio: f32,
     /// Probability of no speech in the segment. If the value is higher than 1.0 and the `avg_logprob` is below -1, consider this segment silent.
     pub no_speech_prob: f32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -423,4 +452,7 @@ pub struct Word {
     pub start: f32,
     /// End time of the word in seconds.
     pub end: f32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,4 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +29,10 @@ pub struct CreateBatchRequest {
     /// Optional custom metadata for the batch.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<serde_json::Value>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -46,6 +51,10 @@ pub struct ListBatchRequest {
     /// A limit on the number of objects to be returned. Limit can range between 1 and 100, and the default is 20.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -77,10 +86,16 @@ pub struct BatchResponse {
     cancelled_at: Option<i64>,
     request_counts: RequestCounts,
     metadata: serde_json::Value,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ListBatchResponse {
     object: String,
     data: Vec<BatchResponse>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,10 +1,16 @@
 #[allow(unused)]
 use crate::apis;
-use crate::shared::response_wrapper::OpenAIError;
-use crate::shared::types::Stop;
+use crate::interfaces::assistants::JsonSchema;
+use crate::interfaces::audio::Voice;
+use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use crate::shared::types::{Extra, Stop, StreamOptions};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use derive_builder::Builder;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, strum::Display)]
 #[serde(rename_all = "lowercase")]
@@ -16,6 +22,18 @@ pub enum Role {
     User,
     #[strum(serialize = "assistant")]
     Assistant,
+    #[strum(serialize = "tool")]
+    Tool,
+    #[strum(serialize = "developer")]
+    Developer,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolType {
+    #[default]
+    #[strum(serialize = "function")]
+    Function,
 }
 
 #[derive(Builder, Default, Debug, Clone, Deserialize, Serialize)]
@@ -25,14 +43,495 @@ pub enum Role {
 #[builder(derive(Debug))]
 #[builder(build_fn(error = "OpenAIError"))]
 pub struct ChatCompletionMessage {
-    /// The role of the author of this message. One of `system`, `user`, or `assistant`.
+    /// The role of the author of this message. One of `developer`, `system`, `user`, `assistant`, or `tool`.
     pub role: Role,
 
-    /// The contents of the message.
-    pub content: String,
+    /// The contents of the message, `null` for assistant messages that only call tools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Content>,
 
     /// The name of the author of this message. May contain a-z, A-Z, 0-9, and underscores, with a maximum length of 64 characters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+
+    /// The refusal message of the assistant, if it declined to answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<String>,
+
+    /// The tool calls generated by the model, such as function calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+
+    /// The tool call this message responds to, required for messages of the `tool` role.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+
+    /// The audio of the assistant, if the `audio` output modality was requested.
+    ///
+    /// To send the message back in a later request, replace it with [`ChatCompletionAudio::to_reference`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatCompletionAudio>,
+
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum Modality {
+    #[strum(serialize = "text")]
+    Text,
+    #[strum(serialize = "audio")]
+    Audio,
+}
+
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[builder(name = "ChatAudioOptionsBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ChatAudioOptions {
+    /// The voice the model uses to respond.
+    pub voice: Voice,
+
+    /// The format of the output audio. Streaming only supports `pcm16`.
+    pub format: ChatAudioFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatAudioFormat {
+    #[default]
+    #[strum(serialize = "wav")]
+    Wav,
+    #[strum(serialize = "mp3")]
+    Mp3,
+    #[strum(serialize = "aac")]
+    Aac,
+    #[strum(serialize = "flac")]
+    Flac,
+    #[strum(serialize = "opus")]
+    Opus,
+    /// Raw 16-bit little-endian samples at 24kHz, without a header.
+    #[strum(serialize = "pcm16")]
+    Pcm16,
+}
+
+/// The audio response of the model.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ChatCompletionAudio {
+    /// Identifies the audio in later requests of a multi-turn conversation.
+    #[serde(default)]
+    pub id: String,
+
+    /// Base64 encoded audio bytes in the requested format.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub data: String,
+
+    /// The Unix timestamp (in seconds) after which the audio can no longer be referred to by `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+
+    /// The transcript of the audio.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub transcript: String,
+}
+
+impl ChatCompletionAudio {
+    /// Refers to the audio of an earlier response by its `id`, for the assistant message of a later request.
+    pub fn reference<I>(id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// This audio as a [reference](Self::reference), without the data the API does not accept back.
+    pub fn to_reference(&self) -> Self {
+        Self::reference(self.id.clone())
+    }
+
+    /// The decoded audio bytes.
+    pub fn decode(&self) -> OpenAIResponse<Vec<u8>> {
+        STANDARD
+            .decode(&self.data)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 audio data: {e}")))
+    }
+
+    /// Writes the decoded audio to `path`, e.g. `answer.wav` for [`ChatAudioFormat::Wav`].
+    pub async fn write<P>(&self, path: P) -> OpenAIResponse<()>
+    where
+        P: AsRef<Path>,
+    {
+        tokio::fs::write(path, self.decode()?).await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Content {
+    /// The text contents of the message.
+    TextContent(String),
+    /// An array of content parts, e.g. text and images for [vision-capable models](https://platform.openai.com/docs/guides/vision)
+    /// or audio for audio-capable ones.
+    ArrayOfContentParts(Vec<ContentPart>),
+}
+
+impl Content {
+    /// The text of the message, the text parts joined if it has several.
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            Content::TextContent(text) => Cow::Borrowed(text),
+            Content::ArrayOfContentParts(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for Content {
+    fn default() -> Self {
+        Content::TextContent("".to_string())
+    }
+}
+
+impl From<&str> for Content {
+    fn from(text: &str) -> Self {
+        Content::TextContent(text.into())
+    }
+}
+
+impl From<String> for Content {
+    fn from(text: String) -> Self {
+        Content::TextContent(text)
+    }
+}
+
+impl From<Vec<ContentPart>> for Content {
+    fn from(parts: Vec<ContentPart>) -> Self {
+        Content::ArrayOfContentParts(parts)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+    InputAudio { input_audio: InputAudio },
+    File { file: FileContent },
+}
+
+impl ContentPart {
+    pub fn text<T>(text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        ContentPart::Text { text: text.into() }
+    }
+
+    /// An image by its URL, which may be a `data:` URL.
+    pub fn image_url<U>(url: U) -> Self
+    where
+        U: Into<String>,
+    {
+        ContentPart::ImageUrl {
+            image_url: ImageUrl {
+                url: url.into(),
+                detail: None,
+            },
+        }
+    }
+
+    /// An image sent inline as a base64 `data:` URL, `mime` being e.g. `image/png`.
+    pub fn image_data(bytes: &[u8], mime: &str) -> Self {
+        Self::image_url(data_url(bytes, mime))
+    }
+
+    /// Reads a PNG, JPEG, GIF or WebP image, picked by the file extension, into a `data:` URL.
+    pub async fn image_file<P>(path: P) -> OpenAIResponse<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mime = match extension(path).as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            _ => {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "unsupported image type: {}",
+                    path.display()
+                )))
+            }
+        };
+
+        Ok(Self::image_data(&tokio::fs::read(path).await?, mime))
+    }
+
+    /// Sets how detailed the model sees an image part, other parts are returned as they are.
+    pub fn detail(mut self, detail: ImageDetail) -> Self {
+        if let ContentPart::ImageUrl { image_url } = &mut self {
+            image_url.detail = Some(detail);
+        }
+        self
+    }
+
+    /// Audio sent inline, base64 encoded.
+    pub fn input_audio(bytes: &[u8], format: InputAudioFormat) -> Self {
+        ContentPart::InputAudio {
+            input_audio: InputAudio {
+                data: STANDARD.encode(bytes),
+                format,
+            },
+        }
+    }
+
+    /// Reads a WAV or MP3 file, picked by the file extension.
+    pub async fn audio_file<P>(path: P) -> OpenAIResponse<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let format = match extension(path).as_str() {
+            "wav" => InputAudioFormat::Wav,
+            "mp3" => InputAudioFormat::Mp3,
+            _ => {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "unsupported audio type: {}",
+                    path.display()
+                )))
+            }
+        };
+
+        Ok(Self::input_audio(&tokio::fs::read(path).await?, format))
+    }
+
+    /// A [file](https://platform.openai.com/docs/api-reference/files) uploaded before, e.g. a PDF.
+    pub fn file_id<I>(file_id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        ContentPart::File {
+            file: FileContent {
+                file_id: Some(file_id.into()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// A file sent inline as a base64 `data:` URL, e.g. a PDF.
+    pub fn file_data<N>(filename: N, bytes: &[u8], mime: &str) -> Self
+    where
+        N: Into<String>,
+    {
+        ContentPart::File {
+            file: FileContent {
+                filename: Some(filename.into()),
+                file_data: Some(data_url(bytes, mime)),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImageUrl {
+    /// Either a URL of the image or the base64 encoded image data as a `data:` URL.
+    pub url: String,
+
+    /// Specifies the detail level of the image. `low` uses fewer tokens, you can opt in to high resolution using `high`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<ImageDetail>, // default: auto
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageDetail {
+    #[default]
+    #[strum(serialize = "auto")]
+    Auto,
+    #[strum(serialize = "low")]
+    Low,
+    #[strum(serialize = "high")]
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InputAudio {
+    /// Base64 encoded audio data.
+    pub data: String,
+    pub format: InputAudioFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum InputAudioFormat {
+    #[strum(serialize = "wav")]
+    Wav,
+    #[strum(serialize = "mp3")]
+    Mp3,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FileContent {
+    /// The ID of an uploaded file to use as input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+
+    /// The name of the file, used when passing the file as a string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+
+    /// The base64 encoded file data, used when passing the file as a string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<String>,
+}
+
+fn data_url(bytes: &[u8], mime: &str) -> String {
+    format!("data:{mime};base64,{}", STANDARD.encode(bytes))
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[builder(name = "ChatCompletionToolBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ChatCompletionTool {
+    /// The type of the tool. Currently, only `function` is supported.
+    pub r#type: ToolType,
+
+    pub function: FunctionDefinition,
+}
+
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize)]
+#[builder(name = "FunctionDefinitionBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct FunctionDefinition {
+    /// The name of the function to be called. Must be a-z, A-Z, 0-9, or contain underscores and dashes, with a maximum length of 64.
+    pub name: String,
+
+    /// A description of what the function does, used by the model to choose when and how to call the function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The parameters the functions accepts, described as a JSON Schema object.
+    /// See the [guide](https://platform.openai.com/docs/guides/function-calling) for examples.
+    ///
+    /// Omitting `parameters` defines a function with an empty parameter list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+
+    /// Whether to enable strict schema adherence when generating the function call.
+    /// If set to true, the model will follow the exact schema defined in the `parameters` field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>, // default: false
+}
+
+/// Controls which (if any) tool is called by the model.
+///
+/// `none` is the default when no tools are present, `auto` is the default if tools are present.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ToolChoice {
+    Mode(ToolChoiceMode),
+    Named(NamedToolChoice),
+}
+
+impl ToolChoice {
+    /// Forces the model to call the function `name`.
+    pub fn function<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self::Named(NamedToolChoice {
+            r#type: ToolType::Function,
+            function: FunctionName { name: name.into() },
+        })
+    }
+}
+
+impl From<ToolChoiceMode> for ToolChoice {
+    fn from(mode: ToolChoiceMode) -> Self {
+        Self::Mode(mode)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, strum::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolChoiceMode {
+    /// The model will not call any tool and instead generates a message.
+    #[strum(serialize = "none")]
+    None,
+    /// The model can pick between generating a message or calling one or more tools.
+    #[strum(serialize = "auto")]
+    Auto,
+    /// The model must call one or more tools.
+    #[strum(serialize = "required")]
+    Required,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NamedToolChoice {
+    pub r#type: ToolType,
+    pub function: FunctionName,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FunctionName {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ToolCall {
+    /// The ID of the tool call, to be sent back as `tool_call_id` of the tool message with its result.
+    pub id: String,
+    pub r#type: ToolType,
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FunctionCall {
+    pub name: String,
+
+    /// The arguments to call the function with, as generated by the model in JSON format.
+    /// Note that the model does not always generate valid JSON, and may hallucinate parameters not defined by your function schema.
+    /// Validate the arguments in your code before calling your function.
+    pub arguments: String,
+}
+
+/// The format the model must output.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Free text, the default.
+    Text,
+    /// JSON mode, which ensures the message is valid JSON. The messages must ask for JSON as well.
+    JsonObject,
+    /// Structured outputs, which ensure the message matches the schema if `strict` is set.
+    JsonSchema { json_schema: JsonSchema },
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -75,10 +574,40 @@ pub struct CreateChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>, // default: false
 
+    /// Options for streamed responses, e.g. to receive the token usage at the end of the stream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+
     /// Up to 4 sequences where the API will stop generating further tokens.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Stop>, // default: null
 
+    /// A list of tools the model may call. Currently, only functions are supported as a tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ChatCompletionTool>>,
+
+    /// Controls which (if any) tool is called by the model, e.g. [`ToolChoiceMode::Required`] or [`ToolChoice::function`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+
+    /// Whether to enable parallel function calling during tool use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>, // default: true
+
+    /// An object specifying the format that the model must output.
+    /// See the [Structured Outputs guide](https://platform.openai.com/docs/guides/structured-outputs), or, with the `schemars` feature, `Chat::create_parsed` to derive the schema from a Rust type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+
+    /// The types of output the model should generate, `["text"]` by default.
+    /// Audio capable models can also generate audio with `["text", "audio"]`, which requires `audio` to be set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<Modality>>,
+
+    /// The voice and format of the audio output, required if `modalities` includes `audio`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatAudioOptions>,
+
     /// The maximum number of tokens to generate in the chat completion.
     ///
     /// The total length of input tokens and generated tokens is limited by the model's context length.
@@ -111,6 +640,10 @@ pub struct CreateChatRequest {
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -119,11 +652,14 @@ pub struct Message {
     pub content: String,
 }
 
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Deserialize, Clone, Default, Serialize)]
 pub struct ChatUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -131,6 +667,9 @@ pub struct ChatChoice {
     pub message: ChatCompletionMessage,
     pub finish_reason: String,
     pub index: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -138,13 +677,55 @@ pub struct ChatResponse {
     pub id: String,
     pub object: String,
     pub created: u32,
+    pub model: String,
     pub choices: Vec<ChatChoice>,
     pub usage: ChatUsage,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// The result of [`Chat::create_parsed`](apis::chat::Chat::create_parsed).
+#[cfg(feature = "schemars")]
+#[derive(Debug, Clone)]
+pub struct ParsedChatResponse<T> {
+    /// The content of the first choice deserialized, `None` if the model refused.
+    pub parsed: Option<T>,
+    /// The refusal message of the first choice, if the model declined to answer.
+    pub refusal: Option<String>,
+    pub response: ChatResponse,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct Delta {
+    /// Only set in the first chunk of a choice.
+    pub role: Option<Role>,
     pub content: Option<String>,
+    pub refusal: Option<String>,
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+    /// The next fragment of the audio, whose `data` is base64 encoded on its own.
+    pub audio: Option<ChatCompletionAudio>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// A fragment of a tool call, the fragments sharing an `index` make up one call.
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct ToolCallDelta {
+    pub index: u32,
+    /// Only set in the first fragment of a call.
+    pub id: Option<String>,
+    pub r#type: Option<ToolType>,
+    pub function: Option<FunctionCallDelta>,
+}
+
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct FunctionCallDelta {
+    /// Only set in the first fragment of a call.
+    pub name: Option<String>,
+    /// The next part of the arguments, to be appended to the previous ones.
+    pub arguments: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -152,6 +733,9 @@ pub struct ChatChoiceStream {
     pub delta: Delta,
     pub finish_reason: Option<String>,
     pub index: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -161,4 +745,461 @@ pub struct ChatStreamResponse {
     pub model: String,
     pub created: u32,
     pub choices: Vec<ChatChoiceStream>,
+    /// Only set in the last chunk, if `stream_options.include_usage` is.
+    pub usage: Option<ChatUsage>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Folds the chunks of [`Chat::create_with_stream`](apis::chat::Chat::create_with_stream) into the [`ChatResponse`] the request would have returned without `stream`.
+///
+/// ```ignore
+/// let mut stream = client.chat().create_with_stream(&req).await?;
+/// let mut accumulator = ChatStreamAccumulator::default();
+///
+/// while let Some(chunk) = stream.next().await {
+///     let chunk = chunk?;
+///     accumulator.push(&chunk)?;
+///
+///     for choice in &chunk.choices {
+///         print!("{}", choice.delta.content.as_deref().unwrap_or_default());
+///     }
+/// }
+///
+/// let res = accumulator.finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChatStreamAccumulator {
+    id: String,
+    created: u32,
+    model: String,
+    choices: BTreeMap<u32, ChatChoiceAccumulator>,
+    usage: Option<ChatUsage>,
+    extra: Extra,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ChatChoiceAccumulator {
+    /// Only sent with the first delta, an assistant message unless it says otherwise.
+    role: Option<Role>,
+    content: String,
+    refusal: Option<String>,
+    tool_calls: BTreeMap<u32, ToolCall>,
+    audio: Option<AudioAccumulator>,
+    finish_reason: Option<String>,
+    extra: Extra,
+    message_extra: Extra,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AudioAccumulator {
+    id: String,
+    data: Vec<u8>,
+    expires_at: Option<u64>,
+    transcript: String,
+}
+
+impl ChatStreamAccumulator {
+    /// Adds the deltas of a chunk to the choices they belong to.
+    ///
+    /// Fails if the audio of a delta is not valid base64, the choice it belongs to is left as it was then.
+    pub fn push(&mut self, chunk: &ChatStreamResponse) -> OpenAIResponse<()> {
+        self.id.clone_from(&chunk.id);
+        self.created = chunk.created;
+        self.model.clone_from(&chunk.model);
+        self.extra.extend(chunk.extra.clone());
+
+        if chunk.usage.is_some() {
+            self.usage.clone_from(&chunk.usage);
+        }
+
+        for choice in &chunk.choices {
+            let delta = &choice.delta;
+            // Every fragment is encoded on its own, so the audio can only be joined once decoded.
+            let audio_data = delta
+                .audio
+                .as_ref()
+                .map(|audio| {
+                    STANDARD.decode(&audio.data).map_err(|e| {
+                        OpenAIError::StreamError(format!(
+                            "invalid base64 audio data in choice {}: {e}",
+                            choice.index
+                        ))
+                    })
+                })
+                .transpose()?;
+
+            let accumulator = self.choices.entry(choice.index).or_default();
+            accumulator.extra.extend(choice.extra.clone());
+            accumulator.message_extra.extend(delta.extra.clone());
+
+            if let Some(role) = &delta.role {
+                accumulator.role = Some(role.clone());
+            }
+
+            if let Some(content) = &delta.content {
+                accumulator.content.push_str(content);
+            }
+
+            if let Some(refusal) = &delta.refusal {
+                accumulator
+                    .refusal
+                    .get_or_insert_with(String::new)
+                    .push_str(refusal);
+            }
+
+            for delta in delta.tool_calls.iter().flatten() {
+                let tool_call = accumulator.tool_calls.entry(delta.index).or_default();
+
+                if let Some(id) = &delta.id {
+                    tool_call.id.clone_from(id);
+                }
+
+                if let Some(r#type) = &delta.r#type {
+                    tool_call.r#type = r#type.clone();
+                }
+
+                if let Some(function) = &delta.function {
+                    if let Some(name) = &function.name {
+                        tool_call.function.name.push_str(name);
+                    }
+
+                    if let Some(arguments) = &function.arguments {
+                        tool_call.function.arguments.push_str(arguments);
+                    }
+                }
+            }
+
+            if let Some(audio) = &delta.audio {
+                let accumulator = accumulator.audio.get_or_insert_with(Default::default);
+
+                if !audio.id.is_empty() {
+                    accumulator.id.clone_from(&audio.id);
+                }
+
+                if audio.expires_at.is_some() {
+                    accumulator.expires_at = audio.expires_at;
+                }
+
+                accumulator.data.extend(audio_data.unwrap_or_default());
+                accumulator.transcript.push_str(&audio.transcript);
+            }
+
+            if choice.finish_reason.is_some() {
+                accumulator.finish_reason.clone_from(&choice.finish_reason);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The token usage reported by the stream, see [`StreamOptions::include_usage`].
+    pub fn usage(&self) -> Option<&ChatUsage> {
+        self.usage.as_ref()
+    }
+
+    /// The response assembled from all chunks pushed so far, `usage` is zero unless the stream reported it.
+    pub fn finish(self) -> ChatResponse {
+        ChatResponse {
+            id: self.id,
+            object: "chat.completion".into(),
+            created: self.created,
+            model: self.model,
+            choices: self
+                .choices
+                .into_iter()
+                .map(|(index, choice)| ChatChoice {
+                    message: ChatCompletionMessage {
+                        role: choice.role.unwrap_or(Role::Assistant),
+                        content: (!choice.content.is_empty())
+                            .then_some(Content::TextContent(choice.content)),
+                        name: None,
+                        refusal: choice.refusal,
+                        tool_calls: (!choice.tool_calls.is_empty())
+                            .then(|| choice.tool_calls.into_values().collect()),
+                        tool_call_id: None,
+                        audio: choice.audio.map(|audio| ChatCompletionAudio {
+                            id: audio.id,
+                            data: STANDARD.encode(audio.data),
+                            expires_at: audio.expires_at,
+                            transcript: audio.transcript,
+                        }),
+                        extra: choice.message_extra,
+                    },
+                    finish_reason: choice.finish_reason.unwrap_or_default(),
+                    index,
+                    extra: choice.extra,
+                })
+                .collect(),
+            usage: self.usage.unwrap_or_default(),
+            extra: self.extra,
+        }
+    }
+
+    /// Drains `stream` and returns the assembled response, or the first error.
+    pub async fn collect<S>(stream: S) -> OpenAIResponse<ChatResponse>
+    where
+        S: Stream<Item = OpenAIResponse<ChatStreamResponse>>,
+    {
+        let mut accumulator = Self::default();
+        let mut stream = std::pin::pin!(stream);
+
+        while let Some(chunk) = stream.next().await {
+            accumulator.push(&chunk?)?;
+        }
+
+        Ok(accumulator.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(choices: serde_json::Value) -> ChatStreamResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "model": "gpt-4o",
+            "created": 1,
+            "choices": choices,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn unknown_fields_of_choices_and_messages_are_kept() {
+        let response: ChatResponse = serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hi", "annotations": [] },
+                "logprobs": null,
+                "finish_reason": "stop",
+            }],
+            "usage": {
+                "prompt_tokens": 1,
+                "completion_tokens": 1,
+                "total_tokens": 2,
+                "prompt_tokens_details": { "cached_tokens": 0 },
+            },
+        }))
+        .unwrap();
+
+        let choice = &response.choices[0];
+        assert!(choice.extra.contains_key("logprobs"));
+        assert_eq!(choice.message.extra["annotations"], serde_json::json!([]));
+        assert!(response.usage.extra.contains_key("prompt_tokens_details"));
+    }
+
+    #[test]
+    fn accumulator_keeps_unknown_fields_of_choices_and_deltas() {
+        let mut accumulator = ChatStreamAccumulator::default();
+        accumulator
+            .push(&chunk(serde_json::json!([{
+                "index": 0,
+                "delta": { "content": "Hi", "annotations": [] },
+                "logprobs": { "content": [] },
+            }])))
+            .unwrap();
+
+        let response = accumulator.finish();
+        assert!(response.choices[0].extra.contains_key("logprobs"));
+        assert!(response.choices[0]
+            .message
+            .extra
+            .contains_key("annotations"));
+    }
+
+    #[test]
+    fn accumulator_defaults_to_an_assistant_message() {
+        let mut accumulator = ChatStreamAccumulator::default();
+        accumulator
+            .push(&chunk(
+                serde_json::json!([{ "index": 0, "delta": { "content": "Hi" } }]),
+            ))
+            .unwrap();
+
+        let response = accumulator.finish();
+        assert!(matches!(response.choices[0].message.role, Role::Assistant));
+    }
+
+    #[test]
+    fn accumulator_rejects_audio_that_is_not_base64() {
+        let mut accumulator = ChatStreamAccumulator::default();
+        let invalid = chunk(serde_json::json!([{ "index": 0, "delta": { "audio": {
+            "id": "audio_1", "data": "not base64!", "transcript": "Hi"
+        } } }]));
+
+        let error = accumulator.push(&invalid).unwrap_err();
+
+        assert!(matches!(error, OpenAIError::StreamError(_)));
+        assert!(accumulator.finish().choices.is_empty());
+    }
+
+    #[test]
+    fn accumulator_joins_content_and_tool_calls() {
+        let chunks = [
+            chunk(
+                serde_json::json!([{ "index": 0, "delta": { "role": "assistant", "content": "Hel" } }]),
+            ),
+            chunk(serde_json::json!([{ "index": 0, "delta": { "content": "lo" } }])),
+            chunk(
+                serde_json::json!([{ "index": 1, "delta": { "role": "assistant", "tool_calls": [
+                { "index": 0, "id": "call_1", "type": "function", "function": { "name": "get_weather", "arguments": "{\"ci" } }
+            ] } }]),
+            ),
+            chunk(serde_json::json!([{ "index": 1, "delta": { "tool_calls": [
+                { "index": 0, "function": { "arguments": "ty\":\"Paris\"}" } }
+            ] }, "finish_reason": "tool_calls" }])),
+            chunk(serde_json::json!([{ "index": 0, "delta": {}, "finish_reason": "stop" }])),
+        ];
+
+        let mut accumulator = ChatStreamAccumulator::default();
+        for chunk in &chunks {
+            accumulator.push(chunk).unwrap();
+        }
+        let response = accumulator.finish();
+
+        assert_eq!(response.id, "chatcmpl-1");
+        assert_eq!(response.choices.len(), 2);
+
+        let text = &response.choices[0];
+        assert!(matches!(text.message.role, Role::Assistant));
+        assert_eq!(text.message.content.as_ref().unwrap().text(), "Hello");
+        assert_eq!(text.finish_reason, "stop");
+
+        let tool_calls = response.choices[1].message.tool_calls.as_ref().unwrap();
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].id, "call_1");
+        assert_eq!(tool_calls[0].function.name, "get_weather");
+        assert_eq!(tool_calls[0].function.arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(response.choices[1].finish_reason, "tool_calls");
+    }
+
+    #[test]
+    fn accumulator_keeps_the_usage_of_the_last_chunk() {
+        let mut usage = chunk(serde_json::json!([]));
+        usage.usage = Some(ChatUsage {
+            prompt_tokens: 3,
+            completion_tokens: 2,
+            total_tokens: 5,
+            ..Default::default()
+        });
+
+        let mut accumulator = ChatStreamAccumulator::default();
+        accumulator
+            .push(&chunk(
+                serde_json::json!([{ "index": 0, "delta": { "content": "Hi" } }]),
+            ))
+            .unwrap();
+        accumulator.push(&usage).unwrap();
+
+        assert_eq!(accumulator.usage().unwrap().total_tokens, 5);
+        assert_eq!(accumulator.finish().usage.total_tokens, 5);
+    }
+
+    #[test]
+    fn accumulator_joins_audio_fragments() {
+        let audio = |data: &[u8], transcript: &str| {
+            chunk(serde_json::json!([{ "index": 0, "delta": { "audio": {
+                "id": "audio_1", "data": STANDARD.encode(data), "transcript": transcript
+            } } }]))
+        };
+
+        let mut accumulator = ChatStreamAccumulator::default();
+        accumulator.push(&audio(b"abc", "Hel")).unwrap();
+        accumulator.push(&audio(b"de", "lo")).unwrap();
+
+        let response = accumulator.finish();
+        let audio = response.choices[0].message.audio.as_ref().unwrap();
+        assert_eq!(audio.decode().unwrap(), b"abcde");
+        assert_eq!(audio.transcript, "Hello");
+    }
+
+    #[test]
+    fn content_parts_have_the_wire_shape_of_the_api() {
+        let content = Content::from(vec![
+            ContentPart::text("What is this?"),
+            ContentPart::image_data(b"png", "image/png").detail(ImageDetail::High),
+            ContentPart::image_url("https://example.com/cat.jpg"),
+            ContentPart::input_audio(b"wav", InputAudioFormat::Wav),
+            ContentPart::file_id("file-1"),
+            ContentPart::file_data("a.pdf", b"%PDF", "application/pdf"),
+        ]);
+        let json = serde_json::json!([
+            { "type": "text", "text": "What is this?" },
+            {
+                "type": "image_url",
+                "image_url": { "url": "data:image/png;base64,cG5n", "detail": "high" },
+            },
+            { "type": "image_url", "image_url": { "url": "https://example.com/cat.jpg" } },
+            { "type": "input_audio", "input_audio": { "data": "d2F2", "format": "wav" } },
+            { "type": "file", "file": { "file_id": "file-1" } },
+            {
+                "type": "file",
+                "file": { "filename": "a.pdf", "file_data": "data:application/pdf;base64,JVBERg==" },
+            },
+        ]);
+
+        assert_eq!(serde_json::to_value(&content).unwrap(), json);
+        let parsed: Content = serde_json::from_value(json.clone()).unwrap();
+        assert!(matches!(&parsed, Content::ArrayOfContentParts(parts) if parts.len() == 6));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+        assert_eq!(parsed.text(), "What is this?");
+    }
+
+    #[test]
+    fn text_content_is_a_plain_string() {
+        let content = Content::from("Hi");
+
+        assert_eq!(serde_json::to_value(&content).unwrap(), "Hi");
+        let parsed: Content = serde_json::from_value(serde_json::json!("Hi")).unwrap();
+        assert!(matches!(parsed, Content::TextContent(text) if text == "Hi"));
+    }
+
+    #[tokio::test]
+    async fn files_are_read_by_their_extension() {
+        let dir = std::env::temp_dir().join(format!("rs_openai_content_{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        for name in ["cat.PNG", "cat.jpeg", "hello.Mp3"] {
+            tokio::fs::write(dir.join(name), b"abc").await.unwrap();
+        }
+
+        let png = ContentPart::image_file(dir.join("cat.PNG")).await.unwrap();
+        let jpeg = ContentPart::image_file(dir.join("cat.jpeg")).await.unwrap();
+        let mp3 = ContentPart::audio_file(dir.join("hello.Mp3"))
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert!(
+            matches!(png, ContentPart::ImageUrl { image_url } if image_url.url == "data:image/png;base64,YWJj")
+        );
+        assert!(
+            matches!(jpeg, ContentPart::ImageUrl { image_url } if image_url.url.starts_with("data:image/jpeg;base64,"))
+        );
+        assert!(
+            matches!(mp3, ContentPart::InputAudio { input_audio } if input_audio.format == InputAudioFormat::Mp3 && input_audio.data == "YWJj")
+        );
+    }
+
+    #[tokio::test]
+    async fn files_of_other_types_are_rejected() {
+        for result in [
+            ContentPart::image_file("cat.bmp").await,
+            ContentPart::image_file("cat").await,
+            ContentPart::audio_file("hello.ogg").await,
+        ] {
+            assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+        }
+        assert!(matches!(
+            ContentPart::image_file("does/not/exist.png").await,
+            Err(OpenAIError::Io(_))
+        ));
+    }
 }
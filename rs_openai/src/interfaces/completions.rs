@@ -1,10 +1,11 @@
 #[allow(unused)]
 use crate::apis;
-use crate::shared::response_wrapper::OpenAIError;
-use crate::shared::types::Stop;
+use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use crate::shared::types::{Extra, Stop, StreamOptions};
 use derive_builder::Builder;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
@@ -71,6 +72,10 @@ pub struct CreateCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>, // default: false
 
+    /// Options for streamed responses, e.g. to receive the token usage at the end of the stream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
+
     /// Include the log probabilities on the `logprobs` most likely tokens, as well the chosen tokens.
     /// For example, if `logprobs` is 5, the API will return a list of the 5 most likely tokens.
     /// The API will always return the `logprob` of the sampled token, so there may be up to `logprobs+1` elements in the response.
@@ -126,6 +131,10 @@ pub struct CreateCompletionRequest {
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -136,6 +145,9 @@ pub struct CompletionResponse {
     pub model: String,
     pub choices: Vec<CompletionChoice>,
     pub usage: Usage,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -144,13 +156,19 @@ pub struct CompletionChoice {
     pub index: u32,
     pub logprobs: Option<u8>,
     pub finish_reason: String,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Deserialize, Clone, Default, Serialize)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -159,6 +177,9 @@ pub struct CompletionChoiceStream {
     pub index: usize,
     pub logprobs: Option<u8>,
     pub finish_reason: Option<String>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -168,4 +189,140 @@ pub struct CompletionStreamResponse {
     pub created: u32,
     pub model: String,
     pub choices: Vec<CompletionChoiceStream>,
+    /// Only set in the last chunk, if `stream_options.include_usage` is.
+    pub usage: Option<Usage>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Folds the chunks of [`Completions::create_with_stream`](apis::completions::Completions::create_with_stream) into the [`CompletionResponse`] the request would have returned without `stream`,
+/// see [`ChatStreamAccumulator`](super::chat::ChatStreamAccumulator).
+#[derive(Debug, Clone, Default)]
+pub struct CompletionStreamAccumulator {
+    id: String,
+    created: u32,
+    model: String,
+    choices: BTreeMap<usize, CompletionChoiceStream>,
+    usage: Option<Usage>,
+    extra: Extra,
+}
+
+impl CompletionStreamAccumulator {
+    /// Appends the text of a chunk to the choices it belongs to.
+    pub fn push(&mut self, chunk: &CompletionStreamResponse) {
+        self.id.clone_from(&chunk.id);
+        self.created = chunk.created;
+        self.model.clone_from(&chunk.model);
+        self.extra.extend(chunk.extra.clone());
+
+        if chunk.usage.is_some() {
+            self.usage.clone_from(&chunk.usage);
+        }
+
+        for choice in &chunk.choices {
+            let accumulator =
+                self.choices
+                    .entry(choice.index)
+                    .or_insert_with(|| CompletionChoiceStream {
+                        text: String::new(),
+                        index: choice.index,
+                        logprobs: None,
+                        finish_reason: None,
+                        extra: Extra::new(),
+                    });
+
+            accumulator.text.push_str(&choice.text);
+            accumulator.extra.extend(choice.extra.clone());
+
+            if choice.logprobs.is_some() {
+                accumulator.logprobs = choice.logprobs;
+            }
+
+            if choice.finish_reason.is_some() {
+                accumulator.finish_reason.clone_from(&choice.finish_reason);
+            }
+        }
+    }
+
+    /// The token usage reported by the stream, see [`StreamOptions::include_usage`].
+    pub fn usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// The response assembled from all chunks pushed so far, `usage` is zero unless the stream reported it.
+    pub fn finish(self) -> CompletionResponse {
+        CompletionResponse {
+            id: self.id,
+            object: "text_completion".into(),
+            created: self.created,
+            model: self.model,
+            choices: self
+                .choices
+                .into_values()
+                .map(|choice| CompletionChoice {
+                    text: choice.text,
+                    index: choice.index as u32,
+                    logprobs: choice.logprobs,
+                    finish_reason: choice.finish_reason.unwrap_or_default(),
+                    extra: choice.extra,
+                })
+                .collect(),
+            usage: self.usage.unwrap_or_default(),
+            extra: self.extra,
+        }
+    }
+
+    /// Drains `stream` and returns the assembled response, or the first error.
+    pub async fn collect<S>(stream: S) -> OpenAIResponse<CompletionResponse>
+    where
+        S: Stream<Item = OpenAIResponse<CompletionStreamResponse>>,
+    {
+        let mut accumulator = Self::default();
+        let mut stream = std::pin::pin!(stream);
+
+        while let Some(chunk) = stream.next().await {
+            accumulator.push(&chunk?);
+        }
+
+        Ok(accumulator.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(choices: serde_json::Value) -> CompletionStreamResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "cmpl-1",
+            "object": "text_completion",
+            "created": 1,
+            "model": "gpt-3.5-turbo-instruct",
+            "choices": choices,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn accumulator_joins_the_text_of_every_choice() {
+        let mut accumulator = CompletionStreamAccumulator::default();
+        accumulator.push(&chunk(serde_json::json!([
+            { "text": "Hel", "index": 0, "logprobs": null, "finish_reason": null },
+            { "text": "Bon", "index": 1, "logprobs": null, "finish_reason": null },
+        ])));
+        accumulator.push(&chunk(serde_json::json!([
+            { "text": "lo", "index": 0, "logprobs": null, "finish_reason": "stop" },
+            { "text": "jour", "index": 1, "logprobs": null, "finish_reason": "length" },
+        ])));
+
+        let response = accumulator.finish();
+
+        assert_eq!(response.id, "cmpl-1");
+        assert_eq!(response.object, "text_completion");
+        assert_eq!(response.choices[0].text, "Hello");
+        assert_eq!(response.choices[0].finish_reason, "stop");
+        assert_eq!(response.choices[1].text, "Bonjour");
+        assert_eq!(response.choices[1].index, 1);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +37,10 @@ pub struct CreateEditRequest {
     /// We generally recommend altering this or `temperature` but not both.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>, //  default: 1
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -44,12 +49,18 @@ pub struct EditResponse {
     pub created: u32,
     pub choices: Vec<Choice>,
     pub usage: Usage,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct Choice {
     pub text: String,
     pub index: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -57,4 +68,7 @@ pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -3,6 +3,7 @@
 //! Related guide: [Embeddings](https://platform.openai.com/docs/guides/embeddings)
 
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +34,10 @@ pub struct CreateEmbeddingRequest {
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -41,6 +46,9 @@ pub struct EmbeddingResponse {
     pub data: Vec<EmbeddingData>,
     pub model: String,
     pub usage: Usage,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -48,10 +56,16 @@ pub struct EmbeddingData {
     pub object: String,
     pub embedding: Vec<f32>,
     pub index: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub total_tokens: u32,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
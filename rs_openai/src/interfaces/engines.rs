@@ -1,3 +1,4 @@
+use crate::shared::types::Extra;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -6,10 +7,16 @@ pub struct EngineResponse {
     pub object: String,
     pub owner: String,
     pub ready: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct EngineListResponse {
     pub data: Vec<EngineResponse>,
     pub object: String,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,5 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
-use crate::shared::types::File;
+use crate::shared::types::{Extra, File};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +20,10 @@ pub struct UploadFileRequest {
     /// Use "fine-tune" for [Fine-tuning](https://platform.openai.com/docs/api-reference/fine-tunes).
     /// This allows us to validate the format of the uploaded file.
     pub purpose: String,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -30,12 +34,18 @@ pub struct FileResponse {
     pub created_at: u32,
     pub filename: String,
     pub purpose: String,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct FileListResponse {
     pub data: Vec<FileResponse>,
     pub object: String,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -43,4 +53,7 @@ pub struct DeleteFileResponse {
     pub id: String,
     pub object: String,
     pub deleted: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
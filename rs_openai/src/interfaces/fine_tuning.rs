@@ -1,4 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -55,6 +56,10 @@ pub struct CreateFineTuningRequest {
     /// If a seed is not specified, one will be generated for you.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<f32>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -146,6 +151,9 @@ pub struct FineTuningResponse {
     pub seed: i32,
     /// The Unix timestamp (in seconds) for when the fine-tuning job is estimated to finish. The value will be null if the fine-tuning job is not running.
     pub estimated_finish: Option<i32>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -161,6 +169,9 @@ pub struct ListFineTuningRequest {
     /// Number of fine-tuning jobs to retrieve.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<f32>, // Defaults to 20
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Fine-tuning job event object
@@ -169,6 +180,9 @@ pub struct FineTuningEventResponse {
     pub object: String,
     pub data: Vec<FineTuningEvent>,
     pub has_more: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -219,4 +233,7 @@ pub struct FineTuningCheckpointResponse {
     first_id: String,
     last_id: String,
     has_more: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
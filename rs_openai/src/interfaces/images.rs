@@ -1,5 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
-use crate::shared::types::File;
+use crate::shared::types::{Extra, File};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +56,10 @@ pub struct CreateImageRequest {
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://beta.openai.com/docs/api-reference/authentication)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -93,6 +97,10 @@ pub struct CreateImageEditRequest {
     /// [Learn more](https://beta.openai.com/docs/api-reference/authentication)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -121,18 +129,50 @@ pub struct CreateImageVariationRequest {
     /// [Learn more](https://beta.openai.com/docs/api-reference/authentication)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
-}
 
-#[derive(Debug, Deserialize, Clone, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub enum ImageData {
-    Url(String),
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
 
-    #[serde(rename = "b64_json")]
-    B64Json(String),
+/// A generated image, either `url` or `b64_json` is set depending on the requested [`ResponseFormat`].
+#[derive(Debug, Deserialize, Clone, Default, Serialize)]
+pub struct ImageData {
+    /// The URL of the image, valid for 60 minutes.
+    pub url: Option<String>,
+    /// The base64 encoded image.
+    pub b64_json: Option<String>,
+    /// The prompt the image was generated from, if the model revised the one of the request.
+    pub revised_prompt: Option<String>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
+
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ImageResponse {
     pub created: i64,
     pub data: Vec<ImageData>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_data_keeps_the_revised_prompt() {
+        let response: ImageResponse = serde_json::from_value(serde_json::json!({
+            "created": 1,
+            "data": [{ "url": "https://example.com/1.png", "revised_prompt": "A red fox" }],
+        }))
+        .unwrap();
+
+        let image = &response.data[0];
+        assert_eq!(image.url.as_deref(), Some("https://example.com/1.png"));
+        assert_eq!(image.b64_json, None);
+        assert_eq!(image.revised_prompt.as_deref(), Some("A red fox"));
+    }
 }
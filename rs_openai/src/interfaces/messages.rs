@@ -1,4 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -23,6 +24,9 @@ pub struct CreateMessageRequest {
     /// Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, strum::Display)]
@@ -150,6 +154,9 @@ pub struct MessageResponse {
     /// This can be useful for storing additional information about the object in a structured format.
     /// Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// The reason the message is incomplete.
@@ -175,6 +182,9 @@ pub struct ListMessageRequest {
     pub before: Option<String>,
     /// Filter messages by the run ID that generated them.
     pub run_id: Option<String>,
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -185,6 +195,9 @@ pub struct ListMessageResponse {
     first_id: String,
     last_id: String,
     has_more: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -199,6 +212,9 @@ pub struct ModifyMessageRequest {
     /// Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -206,4 +222,7 @@ pub struct DeleteMessageResponse {
     pub id: String,
     pub object: String,
     pub delete: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,3 +1,4 @@
+use crate::shared::types::Extra;
 use serde::{Deserialize, Serialize};
 
 /// Describes an OpenAI model offering that can be used with the API.
@@ -11,12 +12,18 @@ pub struct ModelResponse {
     pub created: u32,
     /// The organization that owns the model.
     pub owned_by: String,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ListModelResponse {
     pub object: String,
     pub data: Vec<ModelResponse>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -24,4 +31,7 @@ pub struct DeleteModelResponse {
     pub id: String,
     pub object: String,
     pub deleted: bool,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,4 +1,5 @@
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -15,6 +16,10 @@ pub struct CreateModerationRequest {
     /// The content moderation model you would like to use. Learn more in [the moderation guide](https://platform.openai.com/docs/guides/moderation), and learn about available models [here](https://platform.openai.com/docs/models/moderation).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>, // default: "omni-moderation-latest"
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -67,6 +72,9 @@ pub struct ModerationResponse {
     pub model: String,
     /// A list of moderation objects.
     pub results: Vec<ModerationCategory>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -79,6 +87,9 @@ pub struct ModerationCategory {
     pub category_scores: CategoryScores,
     /// A list of the categories along with the input type(s) that the score applies to.
     pub category_applied_input_types: CategoryAppliedInputTypes,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
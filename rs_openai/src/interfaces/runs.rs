@@ -1,5 +1,6 @@
 use super::assistants::ToolResources;
 use crate::shared::response_wrapper::OpenAIError;
+use crate::shared::types::Extra;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -11,10 +12,36 @@ use std::collections::HashMap;
 #[builder(derive(Debug))]
 #[builder(build_fn(error = "OpenAIError"))]
 pub struct CreateRunRequest {
+    /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) to use to execute this run.
+    pub assistant_id: String,
+
+    /// The ID of the [Model](https://platform.openai.com/docs/api-reference/models) to be used to execute this run.
+    /// If a value is provided here, it will override the model associated with the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Overrides the [instructions](https://platform.openai.com/docs/api-reference/assistants/createAssistant) of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// Appends additional instructions at the end of the instructions for the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_instructions: Option<String>,
+
+    /// Set of 16 key-value pairs that can be attached to an object.
+    /// This can be useful for storing additional information about the object in a structured format.
+    /// Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+
     /// A list of additional fields to include in the response. Currently the only supported value is `step_details.tool_calls[*].file_search.results[*].content` to fetch the file search result content.
     /// See the [file search tool documentation](https://platform.openai.com/docs/assistants/tools/file-search/customizing-file-search-settings) for more information.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub includes: Option<Vec<String>>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Serialize)]
@@ -117,15 +144,25 @@ pub struct FileSearchTool {
     pub r#type: String,
 }
 
-/// Represents a thread that contains [messages](https://platform.openai.com/docs/api-reference/messages).
+/// Represents an execution run on a [thread](https://platform.openai.com/docs/api-reference/threads).
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct RunResponse {
     /// The identifier, which can be referenced in API endpoints.
     pub id: String,
-    /// The object type, which is always `thread`.
+    /// The object type, which is always `thread.run`.
     pub object: String,
-    /// The Unix timestamp (in seconds) for when the thread was created.
+    /// The Unix timestamp (in seconds) for when the run was created.
     pub created_at: u64,
+    /// The ID of the [thread](https://platform.openai.com/docs/api-reference/threads) that was executed on as a part of this run.
+    pub thread_id: String,
+    /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) used for execution of this run.
+    pub assistant_id: String,
+    /// The status of the run, which can be either `queued`, `in_progress`, `requires_action`, `cancelling`, `cancelled`, `failed`, `completed`, `incomplete`, or `expired`.
+    pub status: String,
+    /// The model that the [assistant](https://platform.openai.com/docs/api-reference/assistants) used for this run.
+    pub model: String,
+    /// The instructions that the [assistant](https://platform.openai.com/docs/api-reference/assistants) used for this run.
+    pub instructions: String,
     /// Set of 16 key-value pairs that can be attached to an object.
     /// This can be useful for storing additional information about the object in a structured format.
     /// Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
@@ -133,6 +170,9 @@ pub struct RunResponse {
     /// A set of resources that are used by the assistant's tools. The resources are specific to the type of tool.
     /// For example, the code_interpreter tool requires a list of file IDs, while the `file_search` tool requires a list of vector store IDs.
     pub tool_resources: Option<ToolResources>,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -152,11 +192,8 @@ pub struct ModifyRunRequest {
     /// Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
-}
 
-#[derive(Debug, Deserialize, Clone, Serialize)]
-pub struct DeleteRunResponse {
-    pub id: String,
-    pub object: String,
-    pub delete: bool,
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -1,4 +1,7 @@
-use crate::shared::{response_wrapper::OpenAIError, types::File};
+use crate::shared::{
+    response_wrapper::OpenAIError,
+    types::{Extra, File},
+};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +20,10 @@ pub struct UploadFileRequest {
     /// Use "assistants" for [Assistants](https://platform.openai.com/docs/api-reference/assistants) and [Message](https://platform.openai.com/docs/api-reference/messages) files,
     /// "vision" for Assistants image file inputs, "batch" for [Batch API](https://platform.openai.com/docs/guides/batch), and "fine-tune" for [Fine-tuning](https://platform.openai.com/docs/api-reference/fine-tuning).
     pub purpose: String,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -29,6 +36,9 @@ pub struct UploadFileResponse {
     purpose: String,
     status: String,
     expires_at: i64,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -40,6 +50,9 @@ pub struct UploadFileResponse {
 pub struct AddUploadPartRequest {
     /// The chunk of bytes for this Part.
     pub data: File,
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// The upload [Part](https://platform.openai.com/docs/api-reference/uploads/part-object) object.
@@ -49,6 +62,9 @@ pub struct AddUploadPartResponse {
     object: String,
     created_at: i64,
     upload_id: String,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Builder, Clone, Debug, Default, Serialize)]
@@ -64,6 +80,10 @@ pub struct CompleteUploadRequest {
     /// The optional md5 checksum for the file contents to verify if the bytes uploaded matches what you expect.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub md5: Option<String>,
+
+    /// Any other parameter, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -87,4 +107,7 @@ pub struct CompleteUploadResponse {
     status: String,
     expires_at: i64,
     file: CompleteUploadFile,
+    /// Any other field, see [`Extra`].
+    #[serde(flatten)]
+    pub extra: Extra,
 }
@@ -3,38 +3,26 @@
 //! ## Creating client
 //!
 //! ```ignore
-//! use dotenvy::dotenv;
-//! use rs_openai::{OpenAI};
-//! use std::env::var;
+//! use rs_openai::{OpenAI, OpenAIConfigBuilder};
 //!
-//! dotenv().ok();
-//! let api_key = var("OPENAI_API_KEY").unwrap();
+//! // reads OPENAI_API_KEY, OPENAI_BASE_URL, OPENAI_ORG_ID, ... after loading `.env`
+//! let client = OpenAI::from_dotenv()?;
 //!
-//! let client = OpenAI::new(&OpenAI {
-//!     api_key,
-//!     org_id: None,
-//! });
+//! // or configure it explicitly
+//! let client = OpenAI::new(OpenAIConfigBuilder::default().api_key("sk-...").build()?);
 //! ```
 //!
 //! ## Making requests
 //!
 //!```ignore
-//! use dotenvy::dotenv;
 //! use rs_openai::{
 //!     chat::{ChatCompletionMessageRequestBuilder, CreateChatRequestBuilder, Role},
 //!     OpenAI,
 //! };
-//! use std::env::var;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     dotenv().ok();
-//!     let api_key = var("OPENAI_API_KEY").unwrap();
-//!
-//!     let client = OpenAI::new(&OpenAI {
-//!         api_key,
-//!         org_id: None,
-//!     });
+//!     let client = OpenAI::from_dotenv()?;
 //!
 //!     let req = CreateChatRequestBuilder::default()
 //!         .model("gpt-3.5-turbo")
@@ -56,8 +44,30 @@
 //!
 pub mod apis;
 pub mod client;
+pub mod config;
 pub mod interfaces;
+pub mod meta;
+pub mod options;
+pub mod rate_limit;
+pub mod relay;
+pub mod retry;
+#[cfg(feature = "tower")]
+pub mod service;
 pub mod shared;
+pub mod sse;
+pub mod tools;
+pub mod transport;
 
 pub use apis::*;
 pub use client::*;
+pub use config::*;
+pub use meta::{RawResponse, ResponseMeta, WithMeta, WithRawResponse};
+pub use options::{RequestOptions, RequestOptionsBuilder};
+pub use rate_limit::{RateLimitInfo, RateLimiter};
+pub use retry::{RetryPolicy, RetryPolicyBuilder};
+#[cfg(feature = "tower")]
+pub use service::ServiceTransport;
+pub use sse::{EventStream, ServerSentEvent};
+pub use transport::{
+    ByteStream, HttpResponse, HttpTransport, OpenAIRequest, RequestBody, ReqwestTransport,
+};
@@ -0,0 +1,152 @@
+//! The HTTP status and headers of a response, e.g. the `x-request-id` OpenAI support asks for.
+//!
+//! Every API call can be turned into one returning [`WithMeta`] with [`WithRawResponse::with_raw_response`]:
+//!
+//! ```ignore
+//! use rs_openai::WithRawResponse;
+//!
+//! let res = client.chat().create(&req).with_raw_response().await?;
+//! println!("{:?} took {:?}ms", res.meta.request_id, res.meta.processing_ms);
+//!
+//! let chat_response = res.data;
+//! ```
+//!
+//! Streams work the same way, the metadata is the one of the response that opened the stream:
+//!
+//! ```ignore
+//! let res = client.chat().create_with_stream(&req).with_raw_response().await?;
+//! let (mut stream, meta) = res.into_parts();
+//! ```
+
+use crate::rate_limit::RateLimitInfo;
+use crate::shared::response_wrapper::OpenAIResponse;
+use reqwest::{header::HeaderMap, StatusCode};
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::task::futures::TaskLocalFuture;
+
+/// Name for the header carrying the id of the request
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Name for the header carrying how long the API took to process the request
+pub const PROCESSING_MS_HEADER: &str = "openai-processing-ms";
+
+/// Name for the header carrying the model that served the request
+pub const MODEL_HEADER: &str = "openai-model";
+
+#[derive(Debug, Clone, Default)]
+pub struct ResponseMeta {
+    pub status: StatusCode,
+    /// The `x-request-id` header, which identifies the request when contacting OpenAI support.
+    pub request_id: Option<String>,
+    /// The `openai-processing-ms` header.
+    pub processing_ms: Option<u64>,
+    /// The `openai-model` header.
+    pub model: Option<String>,
+    /// The `openai-organization` header, i.e. the organization the request was billed to.
+    pub organization: Option<String>,
+    /// The `x-ratelimit-*` headers, i.e. the rate limits of the api key that sent the request.
+    pub rate_limit: Option<RateLimitInfo>,
+    /// All headers of the response.
+    pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    pub fn new(status: StatusCode, headers: &HeaderMap) -> Self {
+        let header = |name: &str| Some(headers.get(name)?.to_str().ok()?.trim().to_owned());
+
+        Self {
+            status,
+            request_id: header(REQUEST_ID_HEADER),
+            processing_ms: header(PROCESSING_MS_HEADER).and_then(|ms| ms.parse().ok()),
+            model: header(MODEL_HEADER),
+            organization: header(crate::client::ORGANIZATION_HEADER),
+            rate_limit: RateLimitInfo::from_headers(headers),
+            headers: headers.clone(),
+        }
+    }
+}
+
+/// A deserialized response body together with the [`ResponseMeta`] of its response.
+#[derive(Debug, Clone)]
+pub struct WithMeta<T> {
+    pub data: T,
+    pub meta: ResponseMeta,
+}
+
+impl<T> WithMeta<T> {
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    pub fn into_parts(self) -> (T, ResponseMeta) {
+        (self.data, self.meta)
+    }
+}
+
+impl<T> Deref for WithMeta<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for WithMeta<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+type MetaSlot = Arc<Mutex<Option<ResponseMeta>>>;
+
+tokio::task_local! {
+    static RESPONSE_META: MetaSlot;
+}
+
+/// Records the metadata of a response for the [`RawResponse`] the request runs in, if any.
+pub(crate) fn record(status: StatusCode, headers: &HeaderMap) {
+    let _ = RESPONSE_META.try_with(|slot| {
+        *slot.lock().unwrap() = Some(ResponseMeta::new(status, headers));
+    });
+}
+
+/// Adds [`with_raw_response`](WithRawResponse::with_raw_response) to every API call.
+pub trait WithRawResponse<T>: Future<Output = OpenAIResponse<T>> + Sized {
+    /// Returns the response body together with the status and headers of the response.
+    ///
+    /// If the call went through several attempts, the metadata is the one of the last attempt.
+    fn with_raw_response(self) -> RawResponse<Self> {
+        let slot = MetaSlot::default();
+
+        RawResponse {
+            inner: Box::pin(RESPONSE_META.scope(slot.clone(), self)),
+            slot,
+        }
+    }
+}
+
+impl<F, T> WithRawResponse<T> for F where F: Future<Output = OpenAIResponse<T>> {}
+
+/// The future returned by [`WithRawResponse::with_raw_response`].
+pub struct RawResponse<F: Future> {
+    inner: Pin<Box<TaskLocalFuture<MetaSlot, F>>>,
+    slot: MetaSlot,
+}
+
+impl<F, T> Future for RawResponse<F>
+where
+    F: Future<Output = OpenAIResponse<T>>,
+{
+    type Output = OpenAIResponse<WithMeta<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let data = ready!(self.inner.as_mut().poll(cx))?;
+        let meta = self.slot.lock().unwrap().take().unwrap_or_default();
+
+        Poll::Ready(Ok(WithMeta { data, meta }))
+    }
+}
@@ -0,0 +1,116 @@
+//! Settings for individual calls, overriding the [`OpenAIConfig`](crate::OpenAIConfig) of the client.
+//!
+//! [`OpenAI::with_options`](crate::OpenAI::with_options) returns a handle sharing the connection pool and retry policy
+//! of the client, whose requests carry the options, e.g. to call on behalf of another project:
+//!
+//! ```ignore
+//! use rs_openai::RequestOptionsBuilder;
+//! use std::time::Duration;
+//!
+//! let options = RequestOptionsBuilder::default()
+//!     .api_key("sk-...")
+//!     .project_id("proj_...")
+//!     .timeout(Duration::from_secs(10))
+//!     .idempotency_key("order-42")
+//!     .build()?;
+//!
+//! let res = client.with_options(options).chat().create(&req).await?;
+//! ```
+
+use crate::config::Redacted;
+use crate::rate_limit::RateLimiter;
+use crate::shared::response_wrapper::OpenAIError;
+use derive_builder::Builder;
+use reqwest::header::HeaderMap;
+use std::fmt::{self, Debug};
+use std::time::Duration;
+
+#[derive(Builder, Clone, Default)]
+#[builder(name = "RequestOptionsBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct RequestOptions {
+    /// Extra headers, which take precedence over the ones of the config.
+    pub headers: HeaderMap,
+
+    /// Extra query parameters appended to the url.
+    pub query: Vec<(String, String)>,
+
+    /// Total timeout of the request instead of [`OpenAIConfig::timeout`](crate::OpenAIConfig::timeout).
+    pub timeout: Option<Duration>,
+
+    /// The api key instead of [`OpenAIConfig::api_key`](crate::OpenAIConfig::api_key).
+    pub api_key: Option<String>,
+
+    /// The organization instead of [`OpenAIConfig::org_id`](crate::OpenAIConfig::org_id).
+    pub org_id: Option<String>,
+
+    /// The project instead of [`OpenAIConfig::project_id`](crate::OpenAIConfig::project_id).
+    pub project_id: Option<String>,
+
+    /// The limiter of the handle. Without one, the limiter of the config is only used if `api_key` is not set,
+    /// since another key has its own rate limits.
+    pub rate_limiter: Option<RateLimiter>,
+
+    /// Sent as the `Idempotency-Key` header, so the server runs a request at most once however often it is sent.
+    ///
+    /// Only the first call through the handle carries it, including the retries of that call, since every call is a
+    /// different request. Requests carrying one are retried even if
+    /// [`RetryPolicy::retry_non_idempotent`](crate::RetryPolicy::retry_non_idempotent) is not set.
+    pub idempotency_key: Option<String>,
+}
+
+impl Debug for RequestOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestOptions")
+            .field("headers", &self.headers)
+            .field("query", &self.query)
+            .field("timeout", &self.timeout)
+            .field("api_key", &self.api_key.as_deref().map(Redacted))
+            .field("org_id", &self.org_id)
+            .field("project_id", &self.project_id)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("idempotency_key", &self.idempotency_key)
+            .finish()
+    }
+}
+
+impl Debug for RequestOptionsBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestOptionsBuilder")
+            .field("headers", &self.headers)
+            .field("query", &self.query)
+            .field("timeout", &self.timeout)
+            .field(
+                "api_key",
+                &self
+                    .api_key
+                    .as_ref()
+                    .map(|api_key| api_key.as_deref().map(Redacted)),
+            )
+            .field("org_id", &self.org_id)
+            .field("project_id", &self.project_id)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("idempotency_key", &self.idempotency_key)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_does_not_print_the_api_key() {
+        let mut builder = RequestOptionsBuilder::default();
+        builder.api_key("sk-secret").project_id("proj_1");
+        let options = builder.build().unwrap();
+
+        for debug in [format!("{builder:?}"), format!("{options:?}")] {
+            assert!(!debug.contains("sk-secret"), "{debug}");
+            assert!(debug.contains("<redacted>"), "{debug}");
+            assert!(debug.contains("proj_1"), "{debug}");
+        }
+    }
+}
@@ -0,0 +1,259 @@
+//! Rate limits reported by the API, and an optional client-side limiter that delays requests before they are sent.
+//!
+//! Related guide: [Rate limits](https://platform.openai.com/docs/guides/rate-limits)
+
+use reqwest::header::HeaderMap;
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The `x-ratelimit-*` headers of a response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitInfo {
+    /// The maximum number of requests that are permitted before exhausting the rate limit.
+    pub limit_requests: Option<u64>,
+    /// The maximum number of tokens that are permitted before exhausting the rate limit.
+    pub limit_tokens: Option<u64>,
+    /// The remaining number of requests that are permitted before exhausting the rate limit.
+    pub remaining_requests: Option<u64>,
+    /// The remaining number of tokens that are permitted before exhausting the rate limit.
+    pub remaining_tokens: Option<u64>,
+    /// The time until the rate limit (based on requests) resets to its initial state.
+    pub reset_requests: Option<Duration>,
+    /// The time until the rate limit (based on tokens) resets to its initial state.
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parses the `x-ratelimit-*` headers, `None` if the response carries none of them.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+        let count = |name: &str| header(name)?.parse::<u64>().ok();
+        let duration = |name: &str| parse_duration(header(name)?);
+
+        let info = Self {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_requests: duration("x-ratelimit-reset-requests"),
+            reset_tokens: duration("x-ratelimit-reset-tokens"),
+        };
+
+        (info != Self::default()).then_some(info)
+    }
+}
+
+/// Parses durations such as `20ms`, `1s`, `6m0s` or `1h2m3.5s`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|split| *split > 0)?;
+        let (number, tail) = rest.split_at(split);
+        let number = number.parse::<f64>().ok()?;
+
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+
+        total += number
+            * match unit {
+                "h" => 3600.0,
+                "m" => 60.0,
+                "s" => 1.0,
+                "ms" => 0.001,
+                _ => return None,
+            };
+        rest = tail;
+    }
+
+    Duration::try_from_secs_f64(total).ok()
+}
+
+/// A client-side limiter with a requests-per-minute and a tokens-per-minute bucket.
+///
+/// Every request waits until both buckets can cover it, so background workers slow down on their own instead of running into `429`s.
+/// The buckets are also drained to what the API reports in `x-ratelimit-remaining-*`, which keeps them in line with other clients using the same key.
+///
+/// Clones share the same buckets, so one limiter can be handed to several clients.
+#[derive(Clone)]
+pub struct RateLimiter {
+    requests: Option<Arc<Mutex<TokenBucket>>>,
+    tokens: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter, `None` leaves that dimension unlimited.
+    pub fn new(requests_per_minute: Option<u32>, tokens_per_minute: Option<u32>) -> Self {
+        let bucket = |per_minute: u32| Arc::new(Mutex::new(TokenBucket::new(per_minute)));
+
+        Self {
+            requests: requests_per_minute.map(bucket),
+            tokens: tokens_per_minute.map(bucket),
+        }
+    }
+
+    /// Waits until a request costing roughly `tokens` tokens may be sent.
+    pub async fn acquire(&self, tokens: u32) {
+        let wait = [(&self.requests, 1), (&self.tokens, tokens)]
+            .into_iter()
+            .filter_map(|(bucket, amount)| Some(bucket.as_ref()?.lock().unwrap().take(amount)))
+            .max()
+            .unwrap_or_default();
+
+        if !wait.is_zero() {
+            tracing::debug!("rate limiter delays request by {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Drains the buckets to the remaining requests and tokens the API reported.
+    pub fn observe(&self, info: &RateLimitInfo) {
+        for (bucket, remaining) in [
+            (&self.requests, info.remaining_requests),
+            (&self.tokens, info.remaining_tokens),
+        ] {
+            if let (Some(bucket), Some(remaining)) = (bucket, remaining) {
+                bucket.lock().unwrap().drain_to(remaining as f64);
+            }
+        }
+    }
+}
+
+impl Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let per_minute = |bucket: &Option<Arc<Mutex<TokenBucket>>>| {
+            bucket
+                .as_ref()
+                .map(|bucket| bucket.lock().unwrap().capacity as u32)
+        };
+
+        f.debug_struct("RateLimiter")
+            .field("requests_per_minute", &per_minute(&self.requests))
+            .field("tokens_per_minute", &per_minute(&self.tokens))
+            .finish()
+    }
+}
+
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_minute: u32) -> Self {
+        let capacity = per_minute.max(1) as f64;
+
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / 60.0,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    /// Takes `amount` right away, going into debt if needed, and returns how long the caller has to wait for the debt to be paid off.
+    fn take(&mut self, amount: u32) -> Duration {
+        self.refill();
+        self.available -= (amount as f64).min(self.capacity);
+
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / self.refill_per_sec)
+        }
+    }
+
+    fn drain_to(&mut self, remaining: f64) {
+        self.refill();
+        self.available = self.available.min(remaining);
+    }
+}
+
+/// Roughly 4 characters per token for English text, which is good enough for pacing.
+pub(crate) fn estimate_tokens(body_len: usize) -> u32 {
+    (body_len / 4).try_into().unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_duration("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(
+            parse_duration("1h2m3.5s"),
+            Some(Duration::from_secs_f64(3723.5))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        assert_eq!(parse_duration("1d"), None);
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration("10"), None);
+    }
+
+    #[test]
+    fn reads_the_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimitInfo::from_headers(&headers), None);
+
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("59"),
+        );
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6m0s"));
+
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.remaining_requests, Some(59));
+        assert_eq!(info.reset_tokens, Some(Duration::from_secs(360)));
+        assert_eq!(info.limit_requests, None);
+    }
+
+    #[test]
+    fn a_bucket_waits_once_it_is_empty() {
+        let mut bucket = TokenBucket::new(60);
+
+        assert_eq!(bucket.take(60), Duration::ZERO);
+
+        let wait = bucket.take(1);
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn a_bucket_never_charges_more_than_its_capacity() {
+        let mut bucket = TokenBucket::new(60);
+
+        let wait = bucket.take(1_000);
+        assert!(wait <= Duration::from_millis(10), "{wait:?}");
+    }
+
+    #[test]
+    fn a_bucket_is_drained_to_what_the_api_reports() {
+        let mut bucket = TokenBucket::new(60);
+
+        bucket.drain_to(0.0);
+
+        assert!(bucket.take(1) > Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,309 @@
+//! Relays a stream of this crate as the server-sent events of your own server, e.g. to a browser.
+//!
+//! With the `axum` feature, [`SseRelay`] is an `IntoResponse`:
+//!
+//! ```ignore
+//! use rs_openai::relay::SseRelay;
+//!
+//! async fn chat(State(client): State<OpenAI>, Json(req): Json<CreateChatRequest>) -> Result<SseRelay, AppError> {
+//!     Ok(SseRelay::new(client.chat().create_with_stream(&req).await?))
+//! }
+//! ```
+//!
+//! With the `actix` feature, it is a `Responder`:
+//!
+//! ```ignore
+//! #[post("/chat")]
+//! async fn chat(client: web::Data<OpenAI>, req: web::Json<CreateChatRequest>) -> actix_web::Result<SseRelay> {
+//!     let stream = client.chat().create_with_stream(&req).await.map_err(ErrorBadGateway)?;
+//!     Ok(SseRelay::new(stream))
+//! }
+//! ```
+
+use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use crate::sse::{ServerSentEvent, DONE};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use std::convert::Infallible;
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{sleep, Instant, Sleep};
+
+/// The comment sent when no event was sent for the keep-alive interval.
+pub const KEEP_ALIVE: &str = ": keep-alive\n\n";
+
+type ErrorMapper = Box<dyn Fn(&OpenAIError) -> ServerSentEvent + Send + Sync>;
+
+/// Encodes a stream of chunks or [`ServerSentEvent`]s as a `text/event-stream` body.
+///
+/// By default a keep-alive comment is sent every 15 seconds without events, the stream ends with `data: [DONE]`
+/// and errors are sent as `event: error` with `{"error": {"message": ..}}` as the data.
+pub struct SseRelay {
+    events: Pin<Box<dyn Stream<Item = OpenAIResponse<ServerSentEvent>> + Send>>,
+    keep_alive: Option<Duration>,
+    end_event: Option<ServerSentEvent>,
+    map_error: ErrorMapper,
+}
+
+impl SseRelay {
+    /// Relays every chunk of `stream` as an unnamed event with the chunk as JSON data.
+    pub fn new<S, T>(stream: S) -> Self
+    where
+        S: Stream<Item = OpenAIResponse<T>> + Send + 'static,
+        T: Serialize,
+    {
+        Self::from_events(stream.map(|chunk| {
+            let data = serde_json::to_string(&chunk?).map_err(|e| {
+                OpenAIError::InvalidArgument(format!("failed to serialize chunk: {e}"))
+            })?;
+
+            Ok(ServerSentEvent::new(data))
+        }))
+    }
+
+    /// Relays the events of `stream` as they are, e.g. the [`EventStream`](crate::EventStream) of a run.
+    pub fn from_events<S>(stream: S) -> Self
+    where
+        S: Stream<Item = OpenAIResponse<ServerSentEvent>> + Send + 'static,
+    {
+        Self {
+            events: Box::pin(stream),
+            keep_alive: Some(Duration::from_secs(15)),
+            end_event: Some(ServerSentEvent::new(DONE)),
+            map_error: Box::new(|e| {
+                let mut event = ServerSentEvent::new(
+                    serde_json::json!({ "error": { "message": e.to_string() } }).to_string(),
+                );
+                event.event = "error".into();
+                event
+            }),
+        }
+    }
+
+    /// How long to wait for an event before sending a keep-alive comment, `None` sends none.
+    pub fn keep_alive<D>(mut self, interval: D) -> Self
+    where
+        D: Into<Option<Duration>>,
+    {
+        self.keep_alive = interval.into();
+        self
+    }
+
+    /// The event sent after the last one, `None` ends the stream without one.
+    pub fn end_event<E>(mut self, event: E) -> Self
+    where
+        E: Into<Option<ServerSentEvent>>,
+    {
+        self.end_event = event.into();
+        self
+    }
+
+    /// Turns an error of the stream into the event sent in its place.
+    pub fn map_error<F>(mut self, map_error: F) -> Self
+    where
+        F: Fn(&OpenAIError) -> ServerSentEvent + Send + Sync + 'static,
+    {
+        self.map_error = Box::new(map_error);
+        self
+    }
+
+    /// The encoded body, for servers other than axum and actix-web.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, Infallible>> + Send {
+        Body {
+            relay: Some(self),
+            timer: None,
+        }
+    }
+}
+
+impl Debug for SseRelay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SseRelay")
+            .field("keep_alive", &self.keep_alive)
+            .field("end_event", &self.end_event)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Body {
+    /// `None` once the stream has ended.
+    relay: Option<SseRelay>,
+    /// Started when the stream is first waited on.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl Stream for Body {
+    type Item = Result<Bytes, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(relay) = self.relay.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        let event = match relay.events.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => event,
+            Poll::Ready(Some(Err(e))) => (relay.map_error)(&e),
+            Poll::Ready(None) => {
+                let end_event = self.relay.take().and_then(|relay| relay.end_event);
+                return Poll::Ready(end_event.map(|event| Ok(event.to_bytes())));
+            }
+            Poll::Pending => {
+                let Some(interval) = relay.keep_alive else {
+                    return Poll::Pending;
+                };
+
+                let timer = self.timer.get_or_insert_with(|| Box::pin(sleep(interval)));
+                if timer.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                timer.as_mut().reset(Instant::now() + interval);
+                return Poll::Ready(Some(Ok(Bytes::from_static(KEEP_ALIVE.as_bytes()))));
+            }
+        };
+
+        if let (Some(interval), Some(timer)) = (relay.keep_alive, self.timer.as_mut()) {
+            timer.as_mut().reset(Instant::now() + interval);
+        }
+
+        Poll::Ready(Some(Ok(event.to_bytes())))
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for SseRelay {
+    fn into_response(self) -> axum::response::Response {
+        use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+
+        (
+            [
+                (CONTENT_TYPE, "text/event-stream"),
+                (CACHE_CONTROL, "no-cache"),
+            ],
+            axum::body::Body::from_stream(self.into_stream()),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(feature = "actix")]
+impl actix_web::Responder for SseRelay {
+    type Body = actix_web::body::BoxBody;
+
+    fn respond_to(self, _req: &actix_web::HttpRequest) -> actix_web::HttpResponse {
+        use actix_web::http::header::CACHE_CONTROL;
+
+        actix_web::HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header((CACHE_CONTROL, "no-cache"))
+            .streaming(self.into_stream())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    fn event(data: &str) -> OpenAIResponse<ServerSentEvent> {
+        Ok(ServerSentEvent::new(data))
+    }
+
+    async fn body(relay: SseRelay) -> Vec<String> {
+        relay
+            .into_stream()
+            .map(|bytes| String::from_utf8(bytes.unwrap().to_vec()).unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keep_alive_is_sent_while_no_event_arrives() {
+        let events =
+            stream::iter([("a", 10), ("b", 10), ("c", 40)]).then(|(data, secs)| async move {
+                sleep(Duration::from_secs(secs)).await;
+                event(data)
+            });
+
+        let body = body(SseRelay::from_events(events)).await;
+
+        assert_eq!(
+            body,
+            [
+                "data: a\n\n",
+                "data: b\n\n",
+                KEEP_ALIVE,
+                KEEP_ALIVE,
+                "data: c\n\n",
+                "data: [DONE]\n\n",
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keep_alive_can_be_turned_off() {
+        let events = stream::once(async {
+            sleep(Duration::from_secs(60)).await;
+            event("a")
+        });
+
+        let body = body(SseRelay::from_events(events).keep_alive(None)).await;
+
+        assert_eq!(body, ["data: a\n\n", "data: [DONE]\n\n"]);
+    }
+
+    #[tokio::test]
+    async fn end_event_replaces_done() {
+        let mut end = ServerSentEvent::new("bye");
+        end.event = "end".into();
+
+        let without = body(SseRelay::from_events(stream::iter([event("a")])).end_event(None)).await;
+        let custom = body(SseRelay::from_events(stream::iter([event("a")])).end_event(end)).await;
+
+        assert_eq!(without, ["data: a\n\n"]);
+        assert_eq!(custom, ["data: a\n\n", "event: end\ndata: bye\n\n"]);
+    }
+
+    #[tokio::test]
+    async fn errors_are_sent_as_events() {
+        let events = || stream::iter([event("a"), Err(OpenAIError::StreamError("boom".into()))]);
+
+        let default = body(SseRelay::from_events(events())).await;
+        let mapped = body(
+            SseRelay::from_events(events())
+                .map_error(|e| ServerSentEvent::new(format!("oops: {e}"))),
+        )
+        .await;
+
+        assert_eq!(
+            default,
+            [
+                "data: a\n\n",
+                "event: error\ndata: {\"error\":{\"message\":\"stream failed: boom\"}}\n\n",
+                "data: [DONE]\n\n",
+            ]
+        );
+        assert_eq!(
+            mapped,
+            [
+                "data: a\n\n",
+                "data: oops: stream failed: boom\n\n",
+                "data: [DONE]\n\n"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn chunks_are_sent_as_json() {
+        let chunks = stream::iter([Ok(serde_json::json!({ "n": 1 }))]);
+
+        assert_eq!(
+            body(SseRelay::new(chunks)).await,
+            ["data: {\"n\":1}\n\n", "data: [DONE]\n\n"]
+        );
+    }
+}
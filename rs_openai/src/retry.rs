@@ -0,0 +1,276 @@
+//! Automatic retries with exponential backoff for rate limited (`429`) and failed (`5xx`) requests.
+//!
+//! Only idempotent requests (`GET`, `DELETE` and those carrying an `Idempotency-Key`) are retried unless
+//! [`RetryPolicy::retry_non_idempotent`] is set, since retrying a `POST` that reached the server may run it twice.
+
+use crate::client::IDEMPOTENCY_KEY_HEADER;
+use crate::shared::response_wrapper::OpenAIError;
+use crate::transport::OpenAIRequest;
+use backoff::{backoff::Backoff, ExponentialBackoff, ExponentialBackoffBuilder};
+use derive_builder::Builder;
+use reqwest::{header::HeaderMap, Method, StatusCode};
+use std::{future::Future, time::Duration};
+
+/// Name for the header carrying the retry delay in milliseconds
+pub const RETRY_AFTER_MS_HEADER: &str = "retry-after-ms";
+
+#[derive(Builder, Clone, Debug)]
+#[builder(name = "RetryPolicyBuilder")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct RetryPolicy {
+    /// How many times a request is sent at most, including the first attempt. `1` disables retries.
+    pub max_attempts: u32, // default: 3
+
+    /// The delay before the first retry, which grows exponentially for every following retry.
+    pub base_delay: Duration, // default: 500ms
+
+    /// The upper bound of a single delay, including the one the server asks for with `Retry-After`.
+    pub max_delay: Duration, // default: 8s
+
+    /// Randomizes every delay by up to this factor in both directions, e.g. `0.5` turns 1s into anything between 0.5s and 1.5s.
+    pub jitter: f64, // min: 0, max: 1, default: 0.5
+
+    /// Stops retrying once this much time has passed since the first attempt. `None` means no limit.
+    pub max_elapsed_time: Option<Duration>, // default: 60s
+
+    /// Whether to retry non-idempotent requests, e.g. every `POST` such as `Chat::create`.
+    pub retry_non_idempotent: bool, // default: false
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            jitter: 0.5,
+            max_elapsed_time: Some(Duration::from_secs(60)),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+/// The outcome of a single attempt.
+pub(crate) enum Attempt<T> {
+    /// Hand `T` to the caller as is.
+    Done(T),
+    /// Try again after the backoff or the delay the server asked for, or hand `T` to the caller if the policy is exhausted.
+    Retry(T, Option<Duration>),
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.base_delay)
+            .with_max_interval(self.max_delay)
+            .with_randomization_factor(self.jitter)
+            .with_multiplier(2.0)
+            .with_max_elapsed_time(self.max_elapsed_time)
+            .build()
+    }
+
+    pub(crate) fn allows(&self, idempotent: bool) -> bool {
+        self.max_attempts > 1 && (self.retry_non_idempotent || idempotent)
+    }
+
+    /// Runs `operation` until it is done, or the policy runs out of attempts or time.
+    pub(crate) async fn run<T, F, Fut>(&self, idempotent: bool, mut operation: F) -> T
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Attempt<T>>,
+    {
+        let allowed = self.allows(idempotent);
+        let mut backoff = self.backoff();
+        let mut attempt = 1;
+
+        loop {
+            let (value, retry_after) = match operation().await {
+                Attempt::Done(value) => return value,
+                Attempt::Retry(value, retry_after) => (value, retry_after),
+            };
+
+            if !allowed || attempt >= self.max_attempts {
+                return value;
+            }
+
+            let delay = match (backoff.next_backoff(), retry_after) {
+                (None, _) => return value,
+                (Some(_), Some(retry_after)) => retry_after.min(self.max_delay),
+                (Some(delay), None) => delay,
+            };
+
+            if let Some(max_elapsed_time) = self.max_elapsed_time {
+                if backoff.get_elapsed_time() + delay > max_elapsed_time {
+                    return value;
+                }
+            }
+
+            tracing::warn!("attempt {attempt} failed, retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// `GET` and `DELETE` requests, and those the server deduplicates by their `Idempotency-Key`, are safe to send twice.
+pub(crate) fn is_idempotent(request: &OpenAIRequest) -> bool {
+    matches!(request.method, Method::GET | Method::DELETE)
+        || request.headers.contains_key(IDEMPOTENCY_KEY_HEADER)
+}
+
+/// `408`, `409`, `429` and every `5xx` are worth another try.
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT | StatusCode::CONFLICT | StatusCode::TOO_MANY_REQUESTS
+    ) || status.is_server_error()
+}
+
+/// Connection failures and timeouts are worth another try.
+pub(crate) fn is_retryable_error(error: &OpenAIError) -> bool {
+    matches!(error, OpenAIError::Reqwest(error) if error.is_connect() || error.is_timeout())
+}
+
+/// Reads the delay the server asked for from `retry-after-ms` or `Retry-After` (in seconds).
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+
+    header(RETRY_AFTER_MS_HEADER)
+        .map(|millis| millis / 1000.0)
+        .or_else(|| header(reqwest::header::RETRY_AFTER.as_str()))
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::RequestBody;
+    use reqwest::header::HeaderValue;
+
+    fn request(method: Method) -> OpenAIRequest {
+        OpenAIRequest {
+            method,
+            url: "https://api.openai.com/v1/models".parse().unwrap(),
+            headers: HeaderMap::new(),
+            body: RequestBody::Empty,
+            timeout: None,
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.parse().unwrap(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn only_safe_requests_are_idempotent() {
+        assert!(is_idempotent(&request(Method::GET)));
+        assert!(is_idempotent(&request(Method::DELETE)));
+        assert!(!is_idempotent(&request(Method::POST)));
+
+        let mut post = request(Method::POST);
+        post.headers
+            .insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("key"));
+        assert!(is_idempotent(&post));
+    }
+
+    #[test]
+    fn retry_after_prefers_milliseconds() {
+        assert_eq!(
+            retry_after(&headers(&[("retry-after-ms", "250"), ("retry-after", "3")])),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            retry_after(&headers(&[("retry-after", "1.5")])),
+            Some(Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn retry_after_ignores_what_it_cannot_parse() {
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+        assert_eq!(retry_after(&headers(&[("retry-after", "-1")])), None);
+        assert_eq!(retry_after(&headers(&[("retry-after", "1e30")])), None);
+        assert_eq!(retry_after(&headers(&[("retry-after-ms", "NaN")])), None);
+        assert_eq!(
+            retry_after(&headers(&[(
+                "retry-after",
+                "Wed, 21 Oct 2015 07:28:00 GMT"
+            )])),
+            None
+        );
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn run_gives_up_after_max_attempts() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let mut calls = 0;
+
+        let value = policy
+            .run(true, || {
+                calls += 1;
+                let attempt = calls;
+                async move { Attempt::Retry(attempt, None) }
+            })
+            .await;
+
+        assert_eq!(value, 3);
+    }
+
+    #[tokio::test]
+    async fn run_does_not_retry_non_idempotent_requests() {
+        let mut calls = 0;
+
+        RetryPolicy::default()
+            .run(false, || {
+                calls += 1;
+                async { Attempt::Retry((), None) }
+            })
+            .await;
+
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test]
+    async fn run_waits_at_most_max_delay_for_the_server() {
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            max_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let mut calls = 0;
+
+        let run = policy.run(true, || {
+            calls += 1;
+            let attempt = calls;
+            async move { Attempt::Retry(attempt, Some(Duration::from_secs(3600))) }
+        });
+        let value = tokio::time::timeout(Duration::from_secs(5), run).await;
+
+        assert_eq!(value.unwrap(), 2);
+    }
+}
@@ -0,0 +1,84 @@
+//! [`tower::Service`] integration, enabled with the `tower` feature.
+//!
+//! [`ReqwestTransport`] is a `Service<OpenAIRequest>`, so it can be wrapped with any tower layers and handed back to the client:
+//!
+//! ```ignore
+//! use rs_openai::{OpenAI, OpenAIConfig, ReqwestTransport};
+//! use std::time::Duration;
+//! use tower::ServiceBuilder;
+//!
+//! let config = OpenAIConfig::from_env()?;
+//!
+//! let service = ServiceBuilder::new()
+//!     .concurrency_limit(8)
+//!     .timeout(Duration::from_secs(30))
+//!     .service(ReqwestTransport::from_config(&config));
+//!
+//! let client = OpenAI::from_service(config, service);
+//! ```
+//!
+//! The layers sit underneath the client's own [`RetryPolicy`](crate::RetryPolicy) and [`RateLimiter`](crate::RateLimiter),
+//! set `retry_policy` to [`RetryPolicy::never`](crate::RetryPolicy::never) when retrying with a tower layer instead.
+
+use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use crate::transport::{HttpResponse, HttpTransport, OpenAIRequest, ReqwestTransport};
+use futures::future::{self, BoxFuture};
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use tower::{BoxError, Service};
+
+impl Service<OpenAIRequest> for ReqwestTransport {
+    type Response = HttpResponse;
+    type Error = OpenAIError;
+    type Future = BoxFuture<'static, OpenAIResponse<HttpResponse>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpenAIRequest) -> Self::Future {
+        Box::pin(Self::execute(self.client().clone(), request))
+    }
+}
+
+/// An [`HttpTransport`] that sends every request through a tower service stack.
+///
+/// The service is cloned for every request, just like tower's own `Buffer` and `ConcurrencyLimit` expect.
+/// Errors of the stack other than [`OpenAIError`], e.g. a timeout of the `Timeout` layer, become [`OpenAIError::Service`].
+pub struct ServiceTransport<S> {
+    service: Mutex<S>,
+}
+
+impl<S> ServiceTransport<S> {
+    pub fn new(service: S) -> Self {
+        Self {
+            service: Mutex::new(service),
+        }
+    }
+}
+
+impl<S> HttpTransport for ServiceTransport<S>
+where
+    S: Service<OpenAIRequest, Response = HttpResponse> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    fn send(&self, request: OpenAIRequest) -> BoxFuture<'_, OpenAIResponse<HttpResponse>> {
+        let mut service = self.service.lock().unwrap().clone();
+
+        Box::pin(async move {
+            future::poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(into_openai_error)?;
+
+            service.call(request).await.map_err(into_openai_error)
+        })
+    }
+}
+
+fn into_openai_error<E: Into<BoxError>>(error: E) -> OpenAIError {
+    match error.into().downcast::<OpenAIError>() {
+        Ok(error) => *error,
+        Err(error) => OpenAIError::Service(error),
+    }
+}
@@ -1,5 +1,10 @@
 //! Errors originating from API calls, parsing responses, and reading-or-writing to the file system.
+use crate::meta::REQUEST_ID_HEADER;
+use crate::rate_limit::RateLimitInfo;
+use crate::retry::is_retryable_status;
+use reqwest::{header::HeaderMap, StatusCode};
 use serde::Deserialize;
+use std::fmt;
 
 #[derive(Debug, thiserror::Error)]
 pub enum OpenAIError {
@@ -7,25 +12,63 @@ pub enum OpenAIError {
     #[error("http error: {0}")]
     Reqwest(#[from] reqwest::Error),
     /// OpenAI returns error object with details of API call failure
-    // #[error("{}: {}", .0.r#type, .0.message)]
-    #[error("{:?}", .0.error)]
-    ApiError(ApiErrorResponse),
+    #[error("{0}")]
+    ApiError(Box<ApiError>),
     /// Error when a response cannot be deserialized into a Rust type
     #[error("failed to deserialize api response: {0}")]
     JSONDeserialize(serde_json::Error),
     /// Error when trying to stream completions SSE
     #[error("stream failed: {0}")]
     StreamError(String),
+    /// Error when reading or writing a file
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
     /// Error from client side validation
     /// or when builder fails to build request before making API call
     #[error("invalid args: {0}")]
     InvalidArgument(String),
+    /// A required environment variable, e.g. `OPENAI_API_KEY`, is not set
+    #[error("environment variable {0} is not set")]
+    MissingEnvVar(&'static str),
+    /// Error from a middleware of a custom service stack, e.g. a tower `Timeout` layer
+    #[error("service error: {0}")]
+    Service(Box<dyn std::error::Error + Send + Sync>),
+    /// The model still called tools after the maximum number of iterations of a tool run
+    #[error("the model still called tools after {0} iterations")]
+    ToolIterationLimit(usize),
 }
 
-#[derive(Debug, Deserialize)]
+impl OpenAIError {
+    /// The error the API responded with, if any.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            OpenAIError::ApiError(api_error) => Some(api_error.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again may succeed, e.g. after a rate limit, a `5xx` or a connection failure.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OpenAIError::ApiError(api_error) => api_error.is_retryable(),
+            OpenAIError::Reqwest(e) => e.is_connect() || e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Whether the prompt plus the requested completion exceed the context window of the model.
+    pub fn is_context_length_exceeded(&self) -> bool {
+        self.api_error()
+            .is_some_and(ApiError::is_context_length_exceeded)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct ApiErrorDetail {
     pub message: String,
-    pub r#type: String,
+    /// Not sent by every provider, e.g. Azure only sends a `code`.
+    #[serde(default)]
+    pub r#type: Option<String>,
     pub param: Option<serde_json::Value>,
     pub code: Option<serde_json::Value>,
 }
@@ -36,4 +79,192 @@ pub struct ApiErrorResponse {
     pub error: ApiErrorDetail,
 }
 
+/// A response with a non-success status.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub status: StatusCode,
+    /// The `x-request-id` header, which identifies the request when contacting OpenAI support.
+    pub request_id: Option<String>,
+    /// The `x-ratelimit-*` headers, e.g. how long until a `429` resets.
+    pub rate_limit: Option<RateLimitInfo>,
+    /// The error object, `None` if the body is not one, e.g. the HTML page of a proxy.
+    pub error: Option<ApiErrorDetail>,
+    /// The body as it was received.
+    pub body: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> Self {
+        Self {
+            status,
+            request_id: headers
+                .get(REQUEST_ID_HEADER)
+                .and_then(|request_id| request_id.to_str().ok())
+                .map(str::to_owned),
+            rate_limit: RateLimitInfo::from_headers(headers),
+            error: serde_json::from_slice::<ApiErrorResponse>(body)
+                .ok()
+                .map(|response| response.error),
+            body: String::from_utf8_lossy(body).into_owned(),
+        }
+    }
+
+    /// The `code` of the error object, or its `type` if the code is unknown or missing.
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        let error = self.error.as_ref()?;
+        let code = error
+            .code
+            .as_ref()
+            .and_then(serde_json::Value::as_str)
+            .map(parse_code);
+        let r#type = error.r#type.as_deref().map(parse_code);
+        let is_known = |code: &ApiErrorCode| !matches!(code, ApiErrorCode::Other(_));
+
+        match (code, r#type) {
+            (Some(code), _) if is_known(&code) => Some(code),
+            (_, Some(r#type)) if is_known(&r#type) => Some(r#type),
+            (code, r#type) => code.or(r#type),
+        }
+    }
+
+    /// The message of the error object, or the raw body if there is none.
+    pub fn message(&self) -> &str {
+        match &self.error {
+            Some(error) => &error.message,
+            None => &self.body,
+        }
+    }
+
+    /// `408`, `409`, `429` and every `5xx` except running out of quota, which will not go away by waiting.
+    pub fn is_retryable(&self) -> bool {
+        is_retryable_status(self.status) && self.code() != Some(ApiErrorCode::InsufficientQuota)
+    }
+
+    pub fn is_context_length_exceeded(&self) -> bool {
+        self.code() == Some(ApiErrorCode::ContextLengthExceeded)
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.status, self.message())?;
+
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request id: {request_id})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The `code` or `type` of an [`ApiErrorDetail`].
+#[derive(Debug, Clone, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum ApiErrorCode {
+    /// Too many requests or tokens per minute, the `type` is `requests` or `tokens` then.
+    #[strum(
+        to_string = "rate_limit_exceeded",
+        serialize = "requests",
+        serialize = "tokens"
+    )]
+    RateLimitExceeded,
+    /// The monthly quota or the credit balance is used up.
+    InsufficientQuota,
+    /// The prompt plus the requested completion exceed the context window of the model.
+    ContextLengthExceeded,
+    InvalidApiKey,
+    ModelNotFound,
+    InvalidRequestError,
+    ServerError,
+    /// Any code not covered above.
+    #[strum(default)]
+    Other(String),
+}
+
+fn parse_code(code: &str) -> ApiErrorCode {
+    code.parse()
+        .unwrap_or_else(|_| ApiErrorCode::Other(code.to_owned()))
+}
+
 pub type OpenAIResponse<T> = Result<T, OpenAIError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(status: u16, body: &str) -> ApiError {
+        ApiError::new(
+            StatusCode::from_u16(status).unwrap(),
+            &HeaderMap::new(),
+            body.as_bytes(),
+        )
+    }
+
+    #[test]
+    fn code_prefers_a_known_code_over_the_type() {
+        let error = api_error(
+            400,
+            r#"{"error":{"message":"too long","type":"invalid_request_error","code":"context_length_exceeded"}}"#,
+        );
+
+        assert_eq!(error.code(), Some(ApiErrorCode::ContextLengthExceeded));
+        assert!(error.is_context_length_exceeded());
+    }
+
+    #[test]
+    fn code_falls_back_to_the_type() {
+        let error = api_error(
+            429,
+            r#"{"error":{"message":"slow down","type":"tokens","code":null}}"#,
+        );
+        assert_eq!(error.code(), Some(ApiErrorCode::RateLimitExceeded));
+
+        let error = api_error(
+            400,
+            r#"{"error":{"message":"?","type":"brand_new","code":"newer"}}"#,
+        );
+        assert_eq!(error.code(), Some(ApiErrorCode::Other("newer".into())));
+    }
+
+    #[test]
+    fn azure_errors_have_no_type() {
+        let error = api_error(
+            404,
+            r#"{"error":{"code":"DeploymentNotFound","message":"The API deployment for this resource does not exist."}}"#,
+        );
+
+        assert_eq!(
+            error.code(),
+            Some(ApiErrorCode::Other("DeploymentNotFound".into()))
+        );
+        assert_eq!(
+            error.message(),
+            "The API deployment for this resource does not exist."
+        );
+    }
+
+    #[test]
+    fn a_body_that_is_no_error_object_is_kept() {
+        let error = api_error(502, "<html>Bad Gateway</html>");
+
+        assert_eq!(error.code(), None);
+        assert_eq!(error.message(), "<html>Bad Gateway</html>");
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn running_out_of_quota_is_not_retryable() {
+        let rate_limited = api_error(
+            429,
+            r#"{"error":{"message":"slow down","type":"requests","code":"rate_limit_exceeded"}}"#,
+        );
+        let no_quota = api_error(
+            429,
+            r#"{"error":{"message":"no quota","type":"insufficient_quota","code":"insufficient_quota"}}"#,
+        );
+
+        assert!(rate_limited.is_retryable());
+        assert!(!no_quota.is_retryable());
+        assert!(!api_error(401, "{}").is_retryable());
+    }
+}
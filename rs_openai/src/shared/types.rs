@@ -1,5 +1,13 @@
+use bytes::Bytes;
+use reqwest::multipart::{Form, Part};
 use serde::Serialize;
 
+/// Fields of a request or response this crate does not model yet, e.g. a parameter OpenAI released last week.
+///
+/// They are flattened into the JSON object, so `extra` of a request is sent as top-level parameters and
+/// `extra` of a response collects every field not covered by the struct.
+pub type Extra = serde_json::Map<String, serde_json::Value>;
+
 #[derive(Debug, Serialize, Clone, Default)]
 pub struct File {
     pub buffer: Vec<u8>,
@@ -12,3 +20,91 @@ pub enum Stop {
     String(String),
     ArrayOfString(Vec<String>),
 }
+
+/// Options for streamed responses, only set them together with `stream`.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct StreamOptions {
+    /// Sends an extra chunk before `data: [DONE]`, whose `usage` holds the token usage of the whole request and whose `choices` is empty.
+    pub include_usage: bool,
+}
+
+/// A `multipart/form-data` body that can be turned into a [`Form`] as often as needed, e.g. once per retry.
+#[derive(Debug, Clone, Default)]
+pub struct FormData {
+    fields: Vec<(String, FormValue)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FormValue {
+    Text(String),
+    /// The content is shared between clones rather than copied, so retrying a large upload stays cheap.
+    File {
+        filename: String,
+        content: Bytes,
+    },
+}
+
+impl FormData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a text field.
+    pub fn text<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.fields
+            .push((name.into(), FormValue::Text(value.into())));
+        self
+    }
+
+    /// Adds a file field, sent as `application/octet-stream`.
+    pub fn file<N>(mut self, name: N, file: &File) -> Self
+    where
+        N: Into<String>,
+    {
+        self.fields.push((
+            name.into(),
+            FormValue::File {
+                filename: file.filename.clone(),
+                content: Bytes::copy_from_slice(&file.buffer),
+            },
+        ));
+        self
+    }
+
+    /// Adds every entry of `extra` as a text field, strings as they are and other values as JSON.
+    pub fn extra(mut self, extra: &Extra) -> Self {
+        for (name, value) in extra {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+
+            self.fields.push((name.clone(), FormValue::Text(value)));
+        }
+        self
+    }
+
+    /// The fields in the order they were added, e.g. for a custom [`HttpTransport`](crate::HttpTransport).
+    pub fn fields(&self) -> &[(String, FormValue)] {
+        &self.fields
+    }
+
+    pub fn to_form(&self) -> Form {
+        self.fields
+            .iter()
+            .fold(Form::new(), |form, (name, value)| match value {
+                FormValue::Text(text) => form.text(name.clone(), text.clone()),
+                FormValue::File { filename, content } => form.part(
+                    name.clone(),
+                    Part::stream_with_length(content.clone(), content.len() as u64)
+                        .file_name(filename.clone())
+                        .mime_str("application/octet-stream")
+                        .unwrap(),
+                ),
+            })
+    }
+}
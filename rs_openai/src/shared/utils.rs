@@ -15,3 +15,172 @@ pub fn generate_random_string(length: usize) -> String {
         .map(char::from)
         .collect()
 }
+
+/// The JSON Schema of `T`, without the `$schema` and `title` keywords OpenAI has no use for.
+#[cfg(feature = "schemars")]
+pub fn json_schema_for<T>() -> serde_json::Value
+where
+    T: schemars::JsonSchema,
+{
+    let mut schema = schemars::SchemaGenerator::default().into_root_schema_for::<T>();
+    schema.remove("$schema");
+    schema.remove("title");
+    schema.to_value()
+}
+
+/// Adapts a JSON Schema to the subset supported by structured outputs in strict mode:
+/// every object lists all of its properties as required and allows no others,
+/// optional fields stay nullable, and the `format` of numbers, e.g. `uint32`, is dropped.
+///
+/// Only subschemas are visited, so `enum`/`const`/`default` values and property names are left alone.
+pub fn strict_json_schema(schema: &mut serde_json::Value) {
+    use serde_json::Value;
+
+    let Value::Object(object) = schema else {
+        return;
+    };
+
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        properties.values_mut().for_each(strict_json_schema);
+
+        let required = properties.keys().cloned().map(Value::String).collect();
+        object.insert("required".into(), Value::Array(required));
+        object.insert("additionalProperties".into(), Value::Bool(false));
+    }
+
+    let is_number = |ty: &Value| matches!(ty.as_str(), Some("integer" | "number"));
+    let number = match object.get("type") {
+        Some(Value::Array(types)) => types.iter().any(is_number),
+        Some(ty) => is_number(ty),
+        None => false,
+    };
+    if number {
+        object.remove("format");
+    }
+
+    for key in ["$defs", "definitions"] {
+        if let Some(Value::Object(definitions)) = object.get_mut(key) {
+            definitions.values_mut().for_each(strict_json_schema);
+        }
+    }
+
+    for key in ["items", "additionalProperties"] {
+        match object.get_mut(key) {
+            Some(Value::Array(schemas)) => schemas.iter_mut().for_each(strict_json_schema),
+            Some(schema) => strict_json_schema(schema),
+            None => {}
+        }
+    }
+
+    for key in ["anyOf", "oneOf", "allOf", "prefixItems"] {
+        if let Some(Value::Array(schemas)) = object.get_mut(key) {
+            schemas.iter_mut().for_each(strict_json_schema);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schemars::JsonSchema;
+    use serde_json::json;
+
+    fn strict_schema_for<T: JsonSchema>() -> serde_json::Value {
+        let mut schema = schemars::schema_for!(T).to_value();
+        strict_json_schema(&mut schema);
+        schema
+    }
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct Step {
+        explanation: String,
+        output: Option<String>,
+        count: u32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    #[serde(rename_all = "lowercase")]
+    enum Kind {
+        Easy,
+        Hard,
+    }
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct Reasoning {
+        steps: Vec<Step>,
+        kind: Kind,
+        note: Option<Step>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct Tricky {
+        properties: String,
+        items: Vec<String>,
+        r#type: Option<u8>,
+    }
+
+    #[test]
+    fn nested_structs_are_closed_and_fully_required() {
+        let schema = strict_schema_for::<Reasoning>();
+
+        assert_eq!(schema["additionalProperties"], json!(false));
+        assert_eq!(schema["required"], json!(["kind", "note", "steps"]));
+
+        let step = &schema["$defs"]["Step"];
+        assert_eq!(step["additionalProperties"], json!(false));
+        assert_eq!(step["required"], json!(["count", "explanation", "output"]));
+        assert_eq!(
+            step["properties"]["output"]["type"],
+            json!(["string", "null"])
+        );
+        assert!(step["properties"]["count"].get("format").is_none());
+    }
+
+    #[test]
+    fn enums_keep_their_values() {
+        let schema = strict_schema_for::<Reasoning>();
+
+        assert_eq!(
+            schema["$defs"]["Kind"],
+            json!({ "type": "string", "enum": ["easy", "hard"] })
+        );
+    }
+
+    #[test]
+    fn fields_named_like_keywords_are_not_schemas() {
+        let schema = strict_schema_for::<Tricky>();
+        let properties = schema["properties"].as_object().unwrap();
+
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            ["items", "properties", "type"]
+        );
+        assert_eq!(properties["properties"], json!({ "type": "string" }));
+        assert_eq!(schema["required"], json!(["items", "properties", "type"]));
+    }
+
+    #[test]
+    fn const_and_default_values_are_left_alone() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "mode": { "const": { "properties": {} } },
+                "size": { "type": "integer", "format": "uint8", "default": { "properties": {} } },
+            },
+        });
+        strict_json_schema(&mut schema);
+
+        assert_eq!(
+            schema["properties"]["mode"],
+            json!({ "const": { "properties": {} } })
+        );
+        assert_eq!(
+            schema["properties"]["size"],
+            json!({ "type": "integer", "default": { "properties": {} } })
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! Server-sent events of streaming endpoints, for endpoints this crate does not cover yet.
+//!
+//! [`OpenAI::get_events`](crate::OpenAI::get_events) and [`OpenAI::post_events`](crate::OpenAI::post_events) return the raw events,
+//! [`EventStream::decode`] turns them into typed ones. Named events are decoded as `{"event": .., "data": ..}`,
+//! which fits an adjacently tagged enum:
+//!
+//! ```ignore
+//! use futures::StreamExt;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! #[serde(tag = "event", content = "data")]
+//! enum RunEvent {
+//!     #[serde(rename = "thread.message.delta")]
+//!     MessageDelta(serde_json::Value),
+//!     #[serde(rename = "thread.run.completed")]
+//!     RunCompleted(serde_json::Value),
+//!     #[serde(untagged)]
+//!     Other { event: String, data: serde_json::Value },
+//! }
+//!
+//! let mut events = client
+//!     .post_events(&format!("/threads/{thread_id}/runs"), &req)
+//!     .await?
+//!     .decode::<RunEvent>();
+//!
+//! while let Some(event) = events.next().await {
+//!     println!("{:?}", event?);
+//! }
+//! ```
+
+use crate::shared::response_wrapper::{ApiError, ApiErrorResponse, OpenAIError, OpenAIResponse};
+use crate::transport::HttpResponse;
+use bytes::Bytes;
+use eventsource_stream::{Event, Eventsource};
+use futures::{future, Stream, StreamExt};
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::de::DeserializeOwned;
+use std::fmt::{self, Debug};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The name of events sent without an `event` field.
+pub const DEFAULT_EVENT: &str = "message";
+
+/// The data OpenAI sends as the last event of a stream.
+pub const DONE: &str = "[DONE]";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerSentEvent {
+    /// The `event` field, [`DEFAULT_EVENT`] if there was none.
+    pub event: String,
+    /// The `data` fields, joined by newlines.
+    pub data: String,
+    /// The `id` field, empty if there was none.
+    pub id: String,
+    /// The `retry` field.
+    pub retry: Option<Duration>,
+}
+
+impl ServerSentEvent {
+    /// An unnamed event carrying `data`.
+    pub fn new<D>(data: D) -> Self
+    where
+        D: Into<String>,
+    {
+        Self {
+            event: DEFAULT_EVENT.into(),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Encodes the event in the `text/event-stream` format, e.g. to relay it to a browser.
+    ///
+    /// Line breaks are dropped from `event` and `id`, which are single line fields, so they cannot inject fields
+    /// or events of their own. Every line of `data` is sent as a `data` field of its own.
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buffer = String::new();
+        let single_line = |value: &str| value.replace(['\r', '\n'], "");
+
+        if !self.event.is_empty() && self.event != DEFAULT_EVENT {
+            buffer.push_str(&format!("event: {}\n", single_line(&self.event)));
+        }
+
+        if !self.id.is_empty() {
+            buffer.push_str(&format!("id: {}\n", single_line(&self.id)));
+        }
+
+        if let Some(retry) = self.retry {
+            buffer.push_str(&format!("retry: {}\n", retry.as_millis()));
+        }
+
+        // `\r\n`, `\r` and `\n` all end a line of the stream.
+        for line in self.data.replace("\r\n", "\n").split(['\r', '\n']) {
+            buffer.push_str(&format!("data: {line}\n"));
+        }
+
+        buffer.push('\n');
+        buffer.into()
+    }
+}
+
+impl From<Event> for ServerSentEvent {
+    fn from(event: Event) -> Self {
+        Self {
+            event: event.event,
+            data: event.data,
+            id: event.id,
+            retry: event.retry,
+        }
+    }
+}
+
+/// The events of a response as they arrive, together with the status and headers of the response.
+///
+/// Reading from the connection follows polling the stream and dropping it closes the connection.
+/// It ends when the server closes the connection, it is never reconnected.
+pub struct EventStream {
+    status: StatusCode,
+    headers: HeaderMap,
+    events: Pin<Box<dyn Stream<Item = OpenAIResponse<ServerSentEvent>> + Send>>,
+}
+
+impl EventStream {
+    pub fn new(response: HttpResponse) -> Self {
+        let events = response.body.eventsource().map(|event| {
+            event
+                .map(ServerSentEvent::from)
+                .map_err(|e| OpenAIError::StreamError(e.to_string()))
+        });
+
+        Self {
+            status: response.status,
+            headers: response.headers,
+            events: Box::pin(events),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Decodes the data of every event as a `T`, until the data is `[DONE]`.
+    ///
+    /// Named events are decoded as `{"event": .., "data": ..}` instead, see the [module documentation](self).
+    /// An `error` event, or an error object in place of the data, ends the stream with [`OpenAIError::ApiError`].
+    pub fn decode<T>(self) -> Pin<Box<dyn Stream<Item = OpenAIResponse<T>> + Send>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let Self {
+            status,
+            headers,
+            events,
+        } = self;
+
+        let events = events
+            .take_while(|event| future::ready(!matches!(event, Ok(event) if event.data == DONE)))
+            .map(move |event| decode(&event?, status, &headers))
+            .scan(false, |failed, item| {
+                if *failed {
+                    return future::ready(None);
+                }
+
+                *failed = matches!(item, Err(OpenAIError::ApiError(_)));
+                future::ready(Some(item))
+            });
+
+        Box::pin(events)
+    }
+}
+
+impl Stream for EventStream {
+    type Item = OpenAIResponse<ServerSentEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.as_mut().poll_next(cx)
+    }
+}
+
+impl Debug for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decodes an event, or the error the API sent instead, either as an `error` event or as the data of another one.
+fn decode<T>(event: &ServerSentEvent, status: StatusCode, headers: &HeaderMap) -> OpenAIResponse<T>
+where
+    T: DeserializeOwned,
+{
+    let api_error = || {
+        OpenAIError::ApiError(Box::new(ApiError::new(
+            status,
+            headers,
+            event.data.as_bytes(),
+        )))
+    };
+
+    // Checked first, since a lenient `T`, e.g. `serde_json::Value`, would accept the error object as well.
+    if event.event == "error"
+        || (event.data.contains("\"error\"")
+            && serde_json::from_str::<ApiErrorResponse>(&event.data).is_ok())
+    {
+        return Err(api_error());
+    }
+
+    let decoded = match event.event.as_str() {
+        DEFAULT_EVENT => serde_json::from_str(&event.data),
+        name => {
+            let data = serde_json::from_str(&event.data)
+                .unwrap_or_else(|_| serde_json::Value::String(event.data.clone()));
+
+            serde_json::from_value(serde_json::json!({ "event": name, "data": data }))
+        }
+    };
+
+    decoded.map_err(OpenAIError::JSONDeserialize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use serde::Deserialize;
+
+    fn events(body: &'static str) -> EventStream {
+        EventStream::new(HttpResponse::new(StatusCode::OK, HeaderMap::new(), body))
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Counter {
+        n: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "event", content = "data")]
+    enum Event {
+        #[serde(rename = "thread.run.completed")]
+        RunCompleted { id: String },
+    }
+
+    #[test]
+    fn to_bytes_keeps_line_breaks_out_of_single_line_fields() {
+        let event = ServerSentEvent {
+            event: "delta\n\ndata: injected".into(),
+            id: "1\r\nretry: 0".into(),
+            data: "a\r\nb\rc".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            event.to_bytes(),
+            "event: deltadata: injected\nid: 1retry: 0\ndata: a\ndata: b\ndata: c\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn to_bytes_round_trips_through_the_decoder() {
+        let event = ServerSentEvent {
+            event: "thread.run.completed".into(),
+            id: "evt_1".into(),
+            data: "{\n\"id\": \"run_1\"\n}".into(),
+            retry: None,
+        };
+        let body = event.to_bytes();
+
+        let decoded: Vec<ServerSentEvent> =
+            EventStream::new(HttpResponse::new(StatusCode::OK, HeaderMap::new(), body))
+                .try_collect()
+                .await
+                .unwrap();
+
+        assert_eq!(decoded, [event]);
+    }
+
+    #[tokio::test]
+    async fn decode_ends_with_an_error_sent_mid_stream() {
+        let body = "data: {\"n\":1}\n\n\
+                    data: {\"error\":{\"message\":\"overloaded\",\"type\":\"server_error\",\"code\":null}}\n\n\
+                    data: {\"n\":2}\n\n";
+
+        let decoded: Vec<OpenAIResponse<Counter>> = events(body).decode().collect().await;
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].as_ref().unwrap(), &Counter { n: 1 });
+
+        let error = decoded[1].as_ref().unwrap_err().api_error().unwrap();
+        assert_eq!(error.message(), "overloaded");
+    }
+
+    #[tokio::test]
+    async fn decode_fails_on_an_error_object_whatever_the_type() {
+        let body = "data: {\"n\":1}\n\n\
+                    data: {\"error\":{\"message\":\"overloaded\",\"type\":\"server_error\",\"code\":null}}\n\n";
+
+        let decoded: Vec<OpenAIResponse<serde_json::Value>> = events(body).decode().collect().await;
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].as_ref().unwrap(), &serde_json::json!({ "n": 1 }));
+        let error = decoded[1].as_ref().unwrap_err().api_error().unwrap();
+        assert_eq!(error.message(), "overloaded");
+    }
+
+    #[tokio::test]
+    async fn decode_fails_on_an_error_event() {
+        let body = "event: error\ndata: {\"error\":{\"message\":\"boom\",\"type\":\"server_error\",\"code\":null}}\n\n";
+
+        let decoded: Vec<OpenAIResponse<Counter>> = events(body).decode().collect().await;
+
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(
+            decoded[0]
+                .as_ref()
+                .unwrap_err()
+                .api_error()
+                .unwrap()
+                .message(),
+            "boom"
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_stops_at_done() {
+        let body = "data: {\"n\":1}\n\ndata: {\"n\":2}\n\ndata: [DONE]\n\ndata: {\"n\":3}\n\n";
+
+        let decoded: Vec<serde_json::Value> = events(body).decode().try_collect().await.unwrap();
+
+        assert_eq!(
+            decoded,
+            [serde_json::json!({ "n": 1 }), serde_json::json!({ "n": 2 })]
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_wraps_named_events() {
+        let body = "event: thread.run.completed\ndata: {\"id\":\"run_1\"}\n\n";
+
+        let decoded: Vec<Event> = events(body).decode().try_collect().await.unwrap();
+
+        assert_eq!(decoded, [Event::RunCompleted { id: "run_1".into() }]);
+    }
+
+    #[test]
+    fn to_bytes_splits_multiline_data() {
+        let event = ServerSentEvent {
+            event: "delta".into(),
+            data: "a\nb".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(event.to_bytes(), "event: delta\ndata: a\ndata: b\n\n");
+    }
+}
@@ -0,0 +1,346 @@
+//! Rust functions the model can call, see [`Chat::run_until_done`](crate::apis::chat::Chat::run_until_done).
+//!
+//! With the `schemars` feature, the JSON Schema of a function's parameters is derived from its argument type:
+//!
+//! ```ignore
+//! use rs_openai::tools::ToolRegistry;
+//! use schemars::JsonSchema;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, JsonSchema)]
+//! struct GetWeather {
+//!     /// The city, e.g. Paris
+//!     city: String,
+//! }
+//!
+//! let mut registry = ToolRegistry::new();
+//! registry.register("get_weather", "Get the current weather of a city", |args: GetWeather| async move {
+//!     Ok::<_, std::io::Error>(format!("Sunny in {}", args.city))
+//! });
+//!
+//! let run = client.chat().run_until_done(&req, &registry, 5).await?;
+//! println!("{:?}", run.response.choices[0].message.content);
+//! ```
+
+use crate::interfaces::chat::{
+    ChatCompletionMessage, ChatCompletionTool, ChatResponse, FunctionDefinition, Role, ToolCall,
+    ToolType,
+};
+use futures::future::{self, BoxFuture};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::sync::Arc;
+
+type Handler = Arc<dyn Fn(serde_json::Value) -> BoxFuture<'static, String> + Send + Sync>;
+
+/// The functions offered to the model, each with an async handler taking its typed arguments.
+///
+/// Whatever a call fails with, an unknown function, arguments that do not deserialize into the argument type or
+/// an error of the handler, is sent back to the model as the result of the call, so it can correct itself.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: BTreeMap<String, RegisteredTool>,
+}
+
+#[derive(Clone)]
+struct RegisteredTool {
+    definition: FunctionDefinition,
+    handler: Handler,
+}
+
+/// The outcome of [`Chat::run_until_done`](crate::apis::chat::Chat::run_until_done).
+#[derive(Debug, Clone)]
+pub struct ToolRun {
+    /// The response holding the final answer.
+    pub response: ChatResponse,
+    /// The messages of the request, followed by every tool call of the model, its result and the final answer.
+    pub messages: Vec<ChatCompletionMessage>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` as the function `name`, with the JSON Schema of its parameters derived from `A`.
+    #[cfg(feature = "schemars")]
+    pub fn register<A, F, Fut, R, E>(
+        &mut self,
+        name: &str,
+        description: &str,
+        handler: F,
+    ) -> &mut Self
+    where
+        A: DeserializeOwned + schemars::JsonSchema + Send + 'static,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, E>> + Send + 'static,
+        R: Serialize,
+        E: Display,
+    {
+        let parameters = crate::shared::utils::json_schema_for::<A>();
+        self.register_with_schema(name, description, parameters, handler)
+    }
+
+    /// Registers `handler` as the function `name`, whose parameters are described by the JSON Schema `parameters`.
+    pub fn register_with_schema<A, F, Fut, R, E>(
+        &mut self,
+        name: &str,
+        description: &str,
+        parameters: serde_json::Value,
+        handler: F,
+    ) -> &mut Self
+    where
+        A: DeserializeOwned + Send + 'static,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, E>> + Send + 'static,
+        R: Serialize,
+        E: Display,
+    {
+        let handler = Arc::new(handler);
+        let handler: Handler = Arc::new(move |arguments| {
+            let args = match serde_json::from_value::<A>(arguments) {
+                Ok(args) => args,
+                Err(e) => return Box::pin(future::ready(format!("Error: invalid arguments: {e}"))),
+            };
+
+            let output = handler(args);
+            Box::pin(async move {
+                match output.await {
+                    Ok(output) => match serde_json::to_value(output) {
+                        Ok(serde_json::Value::String(output)) => output,
+                        Ok(output) => output.to_string(),
+                        Err(e) => format!("Error: failed to serialize the result: {e}"),
+                    },
+                    Err(e) => format!("Error: {e}"),
+                }
+            })
+        });
+
+        self.tools.insert(
+            name.into(),
+            RegisteredTool {
+                definition: FunctionDefinition {
+                    name: name.into(),
+                    description: Some(description.into()),
+                    parameters: Some(parameters),
+                    strict: None,
+                },
+                handler,
+            },
+        );
+        self
+    }
+
+    /// The registered functions, to be sent as `tools` of a request.
+    pub fn tools(&self) -> Vec<ChatCompletionTool> {
+        self.tools
+            .values()
+            .map(|tool| ChatCompletionTool {
+                r#type: ToolType::Function,
+                function: tool.definition.clone(),
+            })
+            .collect()
+    }
+
+    /// Runs the handler of `tool_call` and returns the `tool` message carrying its result.
+    pub async fn call(&self, tool_call: &ToolCall) -> ChatCompletionMessage {
+        let content = match self.tools.get(&tool_call.function.name) {
+            None => format!("Error: unknown function {}", tool_call.function.name),
+            Some(tool) => match parse_arguments(&tool_call.function.arguments) {
+                Ok(arguments) => (tool.handler)(arguments).await,
+                Err(e) => format!("Error: invalid arguments: {e}"),
+            },
+        };
+
+        ChatCompletionMessage {
+            role: Role::Tool,
+            content: Some(content.into()),
+            tool_call_id: Some(tool_call.id.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Runs the handlers of all `tool_calls` concurrently and returns their messages in the same order.
+    pub async fn call_all(&self, tool_calls: &[ToolCall]) -> Vec<ChatCompletionMessage> {
+        future::join_all(tool_calls.iter().map(|tool_call| self.call(tool_call))).await
+    }
+}
+
+impl Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Parses the arguments generated by the model, a function without parameters may get none at all.
+fn parse_arguments(arguments: &str) -> serde_json::Result<serde_json::Value> {
+    if arguments.trim().is_empty() {
+        return Ok(serde_json::Value::Object(Default::default()));
+    }
+
+    serde_json::from_str(arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::chat::FunctionCall;
+    use serde::Deserialize;
+    use std::time::Duration;
+
+    #[derive(Deserialize)]
+    struct Add {
+        a: i64,
+        b: i64,
+    }
+
+    #[derive(Deserialize)]
+    struct NoArgs {}
+
+    fn registry() -> ToolRegistry {
+        let mut registry = ToolRegistry::new();
+        registry
+            .register_with_schema(
+                "add",
+                "Adds two numbers",
+                serde_json::json!({"type": "object"}),
+                |args: Add| async move {
+                    // The first call finishes last, its result must still come first.
+                    tokio::time::sleep(Duration::from_millis(10 * args.a.max(0) as u64)).await;
+                    Ok::<_, String>(args.a + args.b)
+                },
+            )
+            .register_with_schema(
+                "ping",
+                "Answers pong",
+                serde_json::json!({"type": "object", "properties": {}}),
+                |_: NoArgs| async { Ok::<_, String>("pong") },
+            )
+            .register_with_schema(
+                "fail",
+                "Always fails",
+                serde_json::json!({"type": "object"}),
+                |_: NoArgs| async { Err::<(), _>("out of order") },
+            );
+        registry
+    }
+
+    fn tool_call(id: &str, name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: id.into(),
+            r#type: ToolType::Function,
+            function: FunctionCall {
+                name: name.into(),
+                arguments: arguments.into(),
+            },
+        }
+    }
+
+    fn results(messages: &[ChatCompletionMessage]) -> Vec<(String, String)> {
+        messages
+            .iter()
+            .map(|message| {
+                assert!(matches!(message.role, Role::Tool));
+                (
+                    message.tool_call_id.clone().unwrap(),
+                    message.content.as_ref().unwrap().text().into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn call_all_answers_in_the_order_of_the_calls() {
+        let messages = registry()
+            .call_all(&[
+                tool_call("call_1", "add", r#"{"a":5,"b":1}"#),
+                tool_call("call_2", "add", r#"{"a":0,"b":2}"#),
+                tool_call("call_3", "ping", "{}"),
+            ])
+            .await;
+
+        assert_eq!(
+            results(&messages),
+            [
+                ("call_1".into(), "6".into()),
+                ("call_2".into(), "2".into()),
+                ("call_3".into(), "pong".into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failures_are_sent_back_as_results() {
+        let messages = registry()
+            .call_all(&[
+                tool_call("call_1", "subtract", r#"{"a":1,"b":2}"#),
+                tool_call("call_2", "add", r#"{"a":1}"#),
+                tool_call("call_3", "add", r#"{"a":1,"#),
+                tool_call("call_4", "fail", "{}"),
+            ])
+            .await;
+        let results = results(&messages);
+
+        assert_eq!(results[0].1, "Error: unknown function subtract");
+        assert!(
+            results[1]
+                .1
+                .starts_with("Error: invalid arguments: missing field `b`"),
+            "{}",
+            results[1].1
+        );
+        assert!(
+            results[2].1.starts_with("Error: invalid arguments:"),
+            "{}",
+            results[2].1
+        );
+        assert_eq!(results[3].1, "Error: out of order");
+        assert_eq!(
+            results
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>(),
+            ["call_1", "call_2", "call_3", "call_4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_arguments_are_an_empty_object() {
+        let registry = registry();
+
+        for arguments in ["", "  ", "{}"] {
+            let message = registry.call(&tool_call("call_1", "ping", arguments)).await;
+            assert_eq!(results(&[message])[0].1, "pong");
+        }
+
+        let message = registry.call(&tool_call("call_1", "add", "")).await;
+        assert!(results(&[message])[0]
+            .1
+            .starts_with("Error: invalid arguments:"));
+    }
+
+    #[test]
+    fn tools_lists_the_registered_functions() {
+        let tools = registry().tools();
+
+        assert_eq!(
+            tools
+                .iter()
+                .map(|tool| tool.function.name.as_str())
+                .collect::<Vec<_>>(),
+            ["add", "fail", "ping"]
+        );
+        assert_eq!(
+            tools[0].function.description.as_deref(),
+            Some("Adds two numbers")
+        );
+        assert_eq!(
+            tools[0].function.parameters,
+            Some(serde_json::json!({"type": "object"}))
+        );
+    }
+}
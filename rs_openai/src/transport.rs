@@ -0,0 +1,370 @@
+//! The HTTP layer underneath [`OpenAI`](crate::OpenAI), which can be swapped for an in-memory fake, a recorded-cassette transport or another HTTP stack.
+//!
+//! ```ignore
+//! use futures::future::BoxFuture;
+//! use reqwest::{header::HeaderMap, StatusCode};
+//! use rs_openai::shared::response_wrapper::OpenAIResponse;
+//! use rs_openai::{HttpResponse, HttpTransport, OpenAI, OpenAIConfigBuilder, OpenAIRequest};
+//!
+//! struct Fake;
+//!
+//! impl HttpTransport for Fake {
+//!     fn send(&self, request: OpenAIRequest) -> BoxFuture<'_, OpenAIResponse<HttpResponse>> {
+//!         Box::pin(async move {
+//!             assert_eq!(request.url.path(), "/v1/models");
+//!             Ok(HttpResponse::new(StatusCode::OK, HeaderMap::new(), r#"{"object":"list","data":[]}"#))
+//!         })
+//!     }
+//! }
+//!
+//! let client = OpenAI::with_transport(OpenAIConfigBuilder::default().build()?, Fake);
+//! ```
+
+use crate::config::OpenAIConfig;
+use crate::shared::response_wrapper::{OpenAIError, OpenAIResponse};
+use crate::shared::types::{FormData, FormValue};
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream, Stream, TryStreamExt};
+use reqwest::{header::HeaderMap, Client, Method, StatusCode, Url};
+use serde::Deserialize;
+use std::fmt::{self, Debug};
+use std::pin::Pin;
+use std::time::Duration;
+
+/// The body of a response, delivered in chunks as they arrive.
+pub type ByteStream = Pin<Box<dyn Stream<Item = OpenAIResponse<Bytes>> + Send>>;
+
+/// A request with the url, authentication and headers already resolved from the [`OpenAIConfig`].
+///
+/// Cloning is cheap, bodies and file contents are shared rather than copied, which is what retries rely on.
+#[derive(Debug, Clone)]
+pub struct OpenAIRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: RequestBody,
+    /// Total timeout of the request, see [`OpenAIConfig::timeout`](crate::OpenAIConfig::timeout).
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum RequestBody {
+    #[default]
+    Empty,
+    /// A serialized JSON body, the `Content-Type` header is already set.
+    Json(Bytes),
+    /// A `multipart/form-data` body, the transport picks the boundary and sets the `Content-Type` header.
+    Multipart(FormData),
+}
+
+impl RequestBody {
+    /// The `model` parameter, e.g. to pick the Azure deployment serving the request.
+    pub(crate) fn model(&self) -> Option<String> {
+        #[derive(Deserialize)]
+        struct Model {
+            model: Option<String>,
+        }
+
+        match self {
+            RequestBody::Empty => None,
+            RequestBody::Json(body) => serde_json::from_slice::<Model>(body).ok()?.model,
+            RequestBody::Multipart(form_data) => {
+                form_data
+                    .fields()
+                    .iter()
+                    .find_map(|(name, value)| match value {
+                        FormValue::Text(model) if name == "model" => Some(model.clone()),
+                        _ => None,
+                    })
+            }
+        }
+    }
+}
+
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: ByteStream,
+}
+
+impl HttpResponse {
+    /// A response with the whole body at hand, e.g. from a fake transport.
+    pub fn new<B>(status: StatusCode, headers: HeaderMap, body: B) -> Self
+    where
+        B: Into<Bytes>,
+    {
+        let body = body.into();
+        Self::streaming(status, headers, stream::once(async move { Ok(body) }))
+    }
+
+    /// A response whose body arrives in chunks, e.g. server-sent events.
+    pub fn streaming<S>(status: StatusCode, headers: HeaderMap, body: S) -> Self
+    where
+        S: Stream<Item = OpenAIResponse<Bytes>> + Send + 'static,
+    {
+        Self {
+            status,
+            headers,
+            body: Box::pin(body),
+        }
+    }
+
+    /// Reads the whole body.
+    pub async fn bytes(self) -> OpenAIResponse<Bytes> {
+        let chunks: Vec<Bytes> = self.body.try_collect().await?;
+
+        Ok(match chunks.len() {
+            1 => chunks.into_iter().next().unwrap(),
+            _ => chunks.concat().into(),
+        })
+    }
+}
+
+impl Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends a single request, the client takes care of retries, rate limits and decoding.
+///
+/// Plain, multipart and streaming requests all go through [`HttpTransport::send`],
+/// streaming responses are read from [`HttpResponse::body`] as the chunks arrive.
+pub trait HttpTransport: Send + Sync + 'static {
+    fn send(&self, request: OpenAIRequest) -> BoxFuture<'_, OpenAIResponse<HttpResponse>>;
+}
+
+/// The default transport, backed by a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// A transport with its own connection pool, applying the `connect_timeout` and `read_timeout` of `config`.
+    pub fn from_config(config: &OpenAIConfig) -> Self {
+        let mut client = Client::builder();
+
+        if let Some(connect_timeout) = config.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
+
+        if let Some(read_timeout) = config.read_timeout {
+            client = client.read_timeout(read_timeout);
+        }
+
+        Self::new(client.build().expect("failed to build http client"))
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub(crate) async fn execute(
+        client: Client,
+        request: OpenAIRequest,
+    ) -> OpenAIResponse<HttpResponse> {
+        let mut builder = client
+            .request(request.method, request.url)
+            .headers(request.headers);
+
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        builder = match request.body {
+            RequestBody::Empty => builder,
+            RequestBody::Json(body) => builder.body(body),
+            RequestBody::Multipart(form_data) => builder.multipart(form_data.to_form()),
+        };
+
+        let response = builder.send().await?;
+
+        Ok(HttpResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: Box::pin(response.bytes_stream().map_err(OpenAIError::from)),
+        })
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: OpenAIRequest) -> BoxFuture<'_, OpenAIResponse<HttpResponse>> {
+        Box::pin(Self::execute(self.client.clone(), request))
+    }
+}
+
+/// An in-memory transport for the tests of the client and everything built on it.
+#[cfg(test)]
+pub(crate) mod fake {
+    use super::*;
+    use crate::config::OpenAIConfigBuilder;
+    use crate::retry::RetryPolicyBuilder;
+    use crate::OpenAI;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    type Canned = (StatusCode, HeaderMap, &'static str);
+
+    /// Answers with the queued responses in order, `200 {}` once they run out, and keeps every request.
+    #[derive(Clone, Default)]
+    pub(crate) struct Fake {
+        responses: Arc<Mutex<VecDeque<Canned>>>,
+        requests: Arc<Mutex<Vec<OpenAIRequest>>>,
+    }
+
+    impl Fake {
+        pub(crate) fn respond(self, status: u16, body: &'static str) -> Self {
+            self.respond_with_headers(status, HeaderMap::new(), body)
+        }
+
+        pub(crate) fn respond_with_headers(
+            self,
+            status: u16,
+            headers: HeaderMap,
+            body: &'static str,
+        ) -> Self {
+            let status = StatusCode::from_u16(status).unwrap();
+            self.responses
+                .lock()
+                .unwrap()
+                .push_back((status, headers, body));
+            self
+        }
+
+        pub(crate) fn requests(&self) -> Vec<OpenAIRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        /// The JSON bodies of all requests, `null` for requests without one.
+        pub(crate) fn json_bodies(&self) -> Vec<serde_json::Value> {
+            self.requests()
+                .iter()
+                .map(|request| match &request.body {
+                    RequestBody::Json(body) => serde_json::from_slice(body).unwrap(),
+                    _ => serde_json::Value::Null,
+                })
+                .collect()
+        }
+    }
+
+    impl HttpTransport for Fake {
+        fn send(&self, request: OpenAIRequest) -> BoxFuture<'_, OpenAIResponse<HttpResponse>> {
+            self.requests.lock().unwrap().push(request);
+            let (status, headers, body) = self.responses.lock().unwrap().pop_front().unwrap_or((
+                StatusCode::OK,
+                HeaderMap::new(),
+                "{}",
+            ));
+
+            Box::pin(async move { Ok(HttpResponse::new(status, headers, body)) })
+        }
+    }
+
+    /// A client sending through `fake`, retrying after 1ms instead of the default backoff.
+    pub(crate) fn client(fake: &Fake) -> OpenAI {
+        let retry_policy = RetryPolicyBuilder::default()
+            .base_delay(Duration::from_millis(1))
+            .build()
+            .unwrap();
+        let config = OpenAIConfigBuilder::default()
+            .api_key("sk-test")
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap();
+
+        OpenAI::with_transport(config, fake.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::{client, Fake};
+    use super::*;
+    use crate::shared::types::File;
+    use reqwest::header::AUTHORIZATION;
+
+    #[tokio::test]
+    async fn bytes_joins_the_chunks_of_a_body() {
+        let chunks = ["{\"a\":", "1}"].map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())));
+        let response =
+            HttpResponse::streaming(StatusCode::OK, HeaderMap::new(), stream::iter(chunks));
+
+        assert_eq!(response.bytes().await.unwrap(), "{\"a\":1}");
+    }
+
+    #[test]
+    fn clones_of_a_form_share_the_file_contents() {
+        let file = File {
+            buffer: vec![0; 1024],
+            filename: "speech.mp3".into(),
+        };
+        let form_data = FormData::new()
+            .text("model", "whisper-1")
+            .file("file", &file);
+        let clone = form_data.clone();
+
+        let content = |form_data: &FormData| match &form_data.fields()[1].1 {
+            FormValue::File { content, .. } => content.as_ptr(),
+            FormValue::Text(_) => panic!("not a file"),
+        };
+        assert_eq!(content(&form_data), content(&clone));
+    }
+
+    #[tokio::test]
+    async fn a_transport_gets_the_resolved_request() {
+        let fake = Fake::default().respond(200, r#"{"object":"list","data":[]}"#);
+
+        let response: serde_json::Value = client(&fake)
+            .post("/embeddings", &serde_json::json!({ "input": "hi" }))
+            .await
+            .unwrap();
+
+        let request = &fake.requests()[0];
+        assert_eq!(response["object"], "list");
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url.as_str(), "https://api.openai.com/v1/embeddings");
+        assert_eq!(request.headers[AUTHORIZATION], "Bearer sk-test");
+        assert_eq!(
+            request.headers[reqwest::header::CONTENT_TYPE],
+            "application/json"
+        );
+        assert_eq!(fake.json_bodies()[0], serde_json::json!({ "input": "hi" }));
+    }
+
+    #[tokio::test]
+    async fn a_transport_gets_multipart_bodies_as_form_data() {
+        let fake = Fake::default();
+        let file = File {
+            buffer: b"RIFF".to_vec(),
+            filename: "speech.wav".into(),
+        };
+
+        client(&fake)
+            .post_form::<serde_json::Value>(
+                "/audio/transcriptions",
+                FormData::new()
+                    .text("model", "whisper-1")
+                    .file("file", &file),
+            )
+            .await
+            .unwrap();
+
+        let RequestBody::Multipart(form_data) = &fake.requests()[0].body else {
+            panic!("not a multipart body");
+        };
+        let names: Vec<_> = form_data
+            .fields()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, ["model", "file"]);
+    }
+}